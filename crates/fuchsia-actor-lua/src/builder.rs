@@ -37,8 +37,9 @@ impl<H: LuaHost> LuaActorBuilder<H> {
   pub fn build(self) -> Result<LuaActor<H>, ActorError> {
     let source = match self.source {
       Some(SourceKind::Inline(s)) => s,
-      Some(SourceKind::Path(p)) => std::fs::read_to_string(&p)
-        .map_err(|e| ActorError::Other(format!("read lua source {}: {e}", p.display())))?,
+      Some(SourceKind::Path(p)) => {
+        std::fs::read_to_string(&p).map_err(|e| ActorError::load(&p, e))?
+      }
       None => {
         return Err(ActorError::Other(
           "LuaActorBuilder requires a source (source or source_from_path)".into(),
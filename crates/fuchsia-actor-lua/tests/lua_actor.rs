@@ -6,7 +6,7 @@ use async_trait::async_trait;
 use fuchsia_actor::{Actor, ActorError, Context, Emitter, Inbox, Message, MessageValue};
 use fuchsia_actor_lua::{DefaultLuaHost, LuaActor};
 use fuchsia_capabilities::http::{AllowedHosts, ReqwestHttp};
-use fuchsia_runtime::{ActorRegistry, Edge, Graph, Node, Orchestrator};
+use fuchsia_runtime::{ActorRegistry, Edge, EdgeKind, Graph, Node, Orchestrator};
 use serde_json::{Value, json};
 use std::sync::{Arc, Mutex};
 
@@ -81,6 +81,7 @@ async fn lua_actor_runs_inline_script_end_to_end() {
     edges: vec![Edge {
       from: "lua".into(),
       to: "rec".into(),
+      kind: EdgeKind::Data,
     }],
   };
 
@@ -106,3 +107,46 @@ async fn lua_actor_runs_inline_script_end_to_end() {
   assert_eq!(v["echoed"], json!(42));
   assert_eq!(v["node"], json!("lua"));
 }
+
+#[test]
+fn missing_script_reports_load_error() {
+  let http = Arc::new(ReqwestHttp::new(AllowedHosts::default()));
+  let dir = std::env::temp_dir().join(format!("fuchsia-lua-missing-{}", std::process::id()));
+  std::fs::create_dir_all(&dir).unwrap();
+  std::fs::write(dir.join("echo-v1.lua"), SCRIPT).unwrap();
+
+  let result = LuaActor::builder(DefaultLuaHost::new(http))
+    .source_from_path(dir.join("echo-v2.lua"))
+    .build();
+  std::fs::remove_dir_all(&dir).unwrap();
+
+  match result {
+    Err(ActorError::Load { path, reason }) => {
+      assert!(path.ends_with("echo-v2.lua"));
+      assert!(reason.contains("echo-v1.lua"), "reason: {reason}");
+    }
+    Err(e) => panic!("expected Load, got {e:?}"),
+    Ok(_) => panic!("expected error, got Ok"),
+  }
+}
+
+#[test]
+fn load_error_reports_directory_problems() {
+  let base = std::env::temp_dir().join(format!("fuchsia-lua-dirs-{}", std::process::id()));
+  std::fs::create_dir_all(&base).unwrap();
+  let not_a_dir = base.join("plain.lua");
+  std::fs::write(&not_a_dir, SCRIPT).unwrap();
+
+  let reason = |path: std::path::PathBuf| match ActorError::load(&path, "read failed") {
+    ActorError::Load { reason, .. } => reason,
+    other => panic!("expected Load, got {other:?}"),
+  };
+  let missing_dir = reason(base.join("nope").join("x.lua"));
+  let under_file = reason(not_a_dir.join("x.lua"));
+  std::fs::remove_dir_all(&base).unwrap();
+
+  assert!(missing_dir.contains("does not exist"), "{missing_dir}");
+  // A file where a directory should be is not "missing"; the real error
+  // stands on its own.
+  assert_eq!(under_file, "read failed");
+}
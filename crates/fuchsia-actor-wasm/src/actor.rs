@@ -51,7 +51,10 @@ impl<H: WasmHost> WasmActor<H> {
 #[async_trait]
 impl<H: WasmHost> Actor for WasmActor<H> {
   async fn run(&self, mut inbox: Inbox, emit: Emitter, ctx: Context) -> Result<(), ActorError> {
-    let mut store = Store::new(&self.engine, self.host.initial_state(emit));
+    let mut store = Store::new(&self.engine, self.host.initial_state(emit, &ctx));
+    // Instantiation runs the component's start functions; this bounds them.
+    // Each guest call below gets a fresh budget; the deadline is relative to
+    // the engine's current epoch.
     store.set_epoch_deadline(self.epoch_deadline);
 
     let bindings = self
@@ -60,12 +63,14 @@ impl<H: WasmHost> Actor for WasmActor<H> {
       .await
       .map_err(|e| ActorError::Other(format!("wasm instantiation failed: {e}")))?;
 
+    store.set_epoch_deadline(self.epoch_deadline);
     match self.host.call_setup(&bindings, &mut store, &ctx).await {
       Err(e) => return Err(ActorError::Other(format!("wasm trap (setup): {e}"))),
       Ok(Err(msg)) => return Err(ActorError::Other(format!("component setup error: {msg}"))),
       Ok(Ok(())) => {}
     }
 
+    let mut trapped = false;
     let loop_result: Result<(), ActorError> = loop {
       let msg = tokio::select! {
         _ = ctx.cancelled() => break Ok(()),
@@ -76,17 +81,27 @@ impl<H: WasmHost> Actor for WasmActor<H> {
         break Ok(());
       };
 
+      store.set_epoch_deadline(self.epoch_deadline);
       match self
         .host
         .call_handle(&bindings, &mut store, &ctx, &msg)
         .await
       {
-        Err(e) => break Err(ActorError::Other(format!("wasm trap (handle): {e}"))),
+        Err(e) => {
+          trapped = true;
+          break Err(ActorError::Other(format!("wasm trap (handle): {e}")));
+        }
         Ok(Err(msg)) => break Err(ActorError::Other(format!("component handle error: {msg}"))),
         Ok(Ok(())) => {}
       }
     };
 
+    // A trap leaves the instance unusable; wasmtime panics if it is entered
+    // again, so there is no teardown after one.
+    if trapped {
+      return loop_result;
+    }
+    store.set_epoch_deadline(self.epoch_deadline);
     match self.host.call_teardown(&bindings, &mut store, &ctx).await {
       Err(e) => tracing::warn!(error = %e, "wasm trap during teardown"),
       Ok(Err(msg)) => tracing::warn!(error = %msg, "component teardown error"),
@@ -6,6 +6,11 @@ use std::sync::Arc;
 use wasmtime::Engine;
 use wasmtime::component::{Component, Linker};
 
+/// Default epoch deadline: far enough out to never fire, and small enough that
+/// wasmtime's `current_epoch + ticks` cannot overflow on a long-ticking
+/// engine.
+const NO_DEADLINE: u64 = u64::MAX / 2;
+
 /// Builds a [`WasmActor`]. Acquired via [`WasmActor::builder`].
 ///
 /// A component source is required (one of `component`, `component_from_path`,
@@ -29,7 +34,7 @@ impl<H: WasmHost> WasmActorBuilder<H> {
       engine,
       host,
       component: None,
-      epoch_deadline: u64::MAX,
+      epoch_deadline: NO_DEADLINE,
     }
   }
 
@@ -52,9 +57,12 @@ impl<H: WasmHost> WasmActorBuilder<H> {
     self
   }
 
-  /// Epoch deadline (in ticks) applied to each fresh `Store`. Defaults to
-  /// `u64::MAX` (effectively no deadline). The host is responsible for
-  /// driving the engine's epoch ticker if timeouts should fire.
+  /// Epoch deadline (in ticks) for each `setup`, `handle`, and `teardown`
+  /// call; the budget is refilled before every call. Defaults to
+  /// effectively no deadline. Timeouts only fire if the engine was built
+  /// with `epoch_interruption(true)` and something advances its epoch —
+  /// see [`EpochTicker`](crate::EpochTicker), whose
+  /// [`ticks_for`](crate::EpochTicker::ticks_for) converts a duration.
   pub fn epoch_deadline(mut self, ticks: u64) -> Self {
     self.epoch_deadline = ticks;
     self
@@ -63,8 +71,13 @@ impl<H: WasmHost> WasmActorBuilder<H> {
   pub fn build(self) -> Result<WasmActor<H>, ActorError> {
     let component = match self.component {
       Some(ComponentSource::Compiled(c)) => c,
-      Some(ComponentSource::Path(p)) => Component::from_file(&self.engine, &p)
-        .map_err(|e| ActorError::Other(format!("compile component from {}: {e}", p.display())))?,
+      Some(ComponentSource::Path(p)) => {
+        // Surface a missing or unreadable file as `Load` rather than a
+        // compile failure.
+        std::fs::metadata(&p).map_err(|e| ActorError::load(&p, e))?;
+        Component::from_file(&self.engine, &p)
+          .map_err(|e| ActorError::Other(format!("compile component from {}: {e}", p.display())))?
+      }
       Some(ComponentSource::Bytes(b)) => Component::new(&self.engine, &b)
         .map_err(|e| ActorError::Other(format!("compile component from bytes: {e}")))?,
       None => {
@@ -80,6 +93,16 @@ impl<H: WasmHost> WasmActorBuilder<H> {
       .add_to_linker(&mut linker)
       .map_err(|e| ActorError::Other(format!("link host imports: {e}")))?;
 
+    // Preflight: type-check imports and exports now so a component built
+    // against the wrong world is rejected here, not at workflow start.
+    let pre = linker
+      .instantiate_pre(&component)
+      .map_err(|e| ActorError::Other(format!("component imports: {e}")))?;
+    self
+      .host
+      .check_exports(&pre)
+      .map_err(|e| ActorError::Other(format!("component exports: {e}")))?;
+
     Ok(WasmActor {
       engine: self.engine,
       component,
@@ -6,12 +6,21 @@ use crate::host::WasmHost;
 use async_trait::async_trait;
 use fuchsia_actor::{Context, Emitter, Message, MessageValue};
 use fuchsia_capabilities::http::{HttpClient, HttpError, HttpRequest, HttpResponse};
+use rand_core::RngCore;
 use std::collections::HashMap;
+use std::io;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::task::Poll;
+use std::time::Duration;
+use tokio::io::AsyncWrite;
 use wasmtime::Store;
-use wasmtime::component::{Component, HasData, Linker};
+use wasmtime::component::{Component, HasData, InstancePre, Linker};
+use wasmtime_wasi::cli::{IsTerminal, StdoutStream};
 use wasmtime_wasi::p2::add_to_linker_async;
-use wasmtime_wasi::{ResourceTable, WasiCtx, WasiCtxBuilder, WasiCtxView, WasiView};
+use wasmtime_wasi::{
+  HostMonotonicClock, HostWallClock, ResourceTable, WasiCtx, WasiCtxBuilder, WasiCtxView, WasiView,
+};
 
 wasmtime::component::bindgen!({
     path: "../../wit",
@@ -123,11 +132,233 @@ impl fuchsia::actor::emit::Host for DefaultHostState {
 #[derive(Clone)]
 pub struct DefaultHost {
   http: Arc<dyn HttpClient>,
+  determinism: Option<Determinism>,
+  mirror_stdio: bool,
 }
 
 impl DefaultHost {
   pub fn new(http: Arc<dyn HttpClient>) -> Self {
-    Self { http }
+    Self {
+      http,
+      determinism: None,
+      mirror_stdio: false,
+    }
+  }
+
+  /// Mirror guest stdout/stderr to `tracing` at debug level, one event per
+  /// line under target `"wasm.component"` with a `stream` field. Lines
+  /// longer than [`MAX_STDIO_LINE`] are split. Off by default, in which case
+  /// guest output is discarded.
+  pub fn mirror_stdio(mut self) -> Self {
+    self.mirror_stdio = true;
+    self
+  }
+
+  /// Run components in deterministic mode: `wasi:random` is fed from
+  /// `determinism.seed` and `wasi:clocks` is frozen at
+  /// `determinism.start`. Each actor's random stream is derived from the
+  /// seed and its node id, so two nodes never draw the same values while
+  /// replays and tests of a recorded input produce identical outputs.
+  pub fn deterministic(mut self, determinism: Determinism) -> Self {
+    self.determinism = Some(determinism);
+    self
+  }
+}
+
+/// Seed and frozen clock for [`DefaultHost::deterministic`].
+#[derive(Clone, Copy, Debug)]
+pub struct Determinism {
+  /// Seeds both the secure and insecure `wasi:random` sources, mixed with
+  /// each actor's node id.
+  pub seed: u64,
+  /// Wall-clock reading (time since the Unix epoch) reported for the whole
+  /// run — typically the recorded start time of the run being replayed.
+  pub start: Duration,
+}
+
+impl Determinism {
+  fn apply(&self, builder: &mut WasiCtxBuilder, node_id: &str) {
+    let seed = self.actor_seed(node_id);
+    builder
+      .secure_random(SplitMix64(seed))
+      .insecure_random(SplitMix64(!seed))
+      .insecure_random_seed(u128::from(seed))
+      .wall_clock(FrozenClock(self.start))
+      .monotonic_clock(FrozenClock(self.start));
+  }
+
+  /// Hashes `(seed, node_id)` through splitmix64, folding in the id's
+  /// length and then eight bytes of it at a time. The length keeps zero
+  /// padding from colliding.
+  fn actor_seed(&self, node_id: &str) -> u64 {
+    let len = u64::try_from(node_id.len()).unwrap_or(u64::MAX);
+    let words = node_id.as_bytes().chunks(8).map(|chunk| {
+      let mut word = [0; 8];
+      word[..chunk.len()].copy_from_slice(chunk);
+      u64::from_le_bytes(word)
+    });
+    std::iter::once(len)
+      .chain(words)
+      .fold(SplitMix64(self.seed).next_u64(), |state, word| {
+        SplitMix64(state ^ word).next_u64()
+      })
+  }
+}
+
+/// splitmix64 generator behind both `wasi:random` sources in
+/// deterministic mode. Nearby seeds give unrelated streams, and the stream
+/// does not repeat for 2^64 draws. That matters for guests minting UUIDs,
+/// where a fixed byte cycle would hand out duplicates.
+struct SplitMix64(u64);
+
+impl RngCore for SplitMix64 {
+  fn next_u32(&mut self) -> u32 {
+    // The high half of a splitmix64 output is the better-mixed half.
+    (self.next_u64() >> 32) as u32
+  }
+
+  fn next_u64(&mut self) -> u64 {
+    self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = self.0;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+  }
+
+  fn fill_bytes(&mut self, dest: &mut [u8]) {
+    rand_core::impls::fill_bytes_via_next(self, dest);
+  }
+
+  fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+    self.fill_bytes(dest);
+    Ok(())
+  }
+}
+
+/// Longest guest stdout/stderr line buffered before it is emitted, in bytes.
+pub const MAX_STDIO_LINE: usize = 8 * 1024;
+
+/// Guest stdout/stderr sink for [`DefaultHost::mirror_stdio`].
+///
+/// WASI may drive the writer from a background task, so the actor's span
+/// (carrying the node id) is captured when the store is built and
+/// re-entered for each event.
+struct TracingStdio {
+  stream: &'static str,
+  span: tracing::Span,
+}
+
+impl IsTerminal for TracingStdio {
+  fn is_terminal(&self) -> bool {
+    false
+  }
+}
+
+impl StdoutStream for TracingStdio {
+  fn async_stream(&self) -> Box<dyn AsyncWrite + Send + Sync> {
+    let stream = self.stream;
+    // Span handles are reference-counted; each stream needs its own.
+    let span = self.span.clone();
+    Box::new(LineWriter::new(move |text: &str| {
+      span.in_scope(|| tracing::debug!(target: "wasm.component", stream, "{text}"));
+    }))
+  }
+}
+
+/// Splits guest output into lines and hands each one to `sink`.
+///
+/// Lines reaching [`MAX_STDIO_LINE`] are cut at the last UTF-8 character
+/// boundary and the trailing partial character carries over into the next
+/// line. Invalid UTF-8 is replaced lossily.
+struct LineWriter<F: FnMut(&str)> {
+  line: Vec<u8>,
+  sink: F,
+}
+
+impl<F: FnMut(&str)> LineWriter<F> {
+  fn new(sink: F) -> Self {
+    Self {
+      line: Vec::new(),
+      sink,
+    }
+  }
+
+  fn emit(&mut self) {
+    if self.line.is_empty() {
+      return;
+    }
+    (self.sink)(&String::from_utf8_lossy(&self.line));
+    self.line.clear();
+  }
+
+  /// Emits an over-long line, keeping an incomplete trailing character.
+  fn cut(&mut self) {
+    let end = match std::str::from_utf8(&self.line) {
+      Err(e) if e.error_len().is_none() => e.valid_up_to(),
+      _ => self.line.len(),
+    };
+    let rest = self.line.split_off(end);
+    self.emit();
+    self.line = rest;
+  }
+}
+
+impl<F: FnMut(&str) + Unpin> AsyncWrite for LineWriter<F> {
+  fn poll_write(
+    self: Pin<&mut Self>,
+    _cx: &mut std::task::Context<'_>,
+    buf: &[u8],
+  ) -> Poll<io::Result<usize>> {
+    let this = self.get_mut();
+    for &byte in buf {
+      if byte == b'\n' {
+        this.emit();
+        continue;
+      }
+      this.line.push(byte);
+      if this.line.len() >= MAX_STDIO_LINE {
+        this.cut();
+      }
+    }
+    Poll::Ready(Ok(buf.len()))
+  }
+
+  fn poll_flush(self: Pin<&mut Self>, _cx: &mut std::task::Context<'_>) -> Poll<io::Result<()>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut std::task::Context<'_>) -> Poll<io::Result<()>> {
+    self.get_mut().emit();
+    Poll::Ready(Ok(()))
+  }
+}
+
+impl<F: FnMut(&str)> Drop for LineWriter<F> {
+  fn drop(&mut self) {
+    self.emit();
+  }
+}
+
+/// Clock that always reports the same instant.
+struct FrozenClock(Duration);
+
+impl HostWallClock for FrozenClock {
+  fn resolution(&self) -> Duration {
+    Duration::from_nanos(1)
+  }
+
+  fn now(&self) -> Duration {
+    self.0
+  }
+}
+
+impl HostMonotonicClock for FrozenClock {
+  fn resolution(&self) -> u64 {
+    1
+  }
+
+  fn now(&self) -> u64 {
+    u64::try_from(self.0.as_nanos()).unwrap_or(u64::MAX)
   }
 }
 
@@ -142,9 +373,32 @@ impl WasmHost for DefaultHost {
     Ok(())
   }
 
-  fn initial_state(&self, emitter: Emitter) -> Self::State {
+  fn check_exports(&self, pre: &InstancePre<Self::State>) -> wasmtime::Result<()> {
+    // `InstancePre` is Arc-backed; bindgen's constructor takes it by value
+    // and is the only export check it exposes.
+    ActorComponentPre::new(pre.clone()).map(|_| ())
+  }
+
+  fn initial_state(&self, emitter: Emitter, ctx: &Context) -> Self::State {
+    let mut wasi = WasiCtxBuilder::new();
+    if let Some(determinism) = &self.determinism {
+      determinism.apply(&mut wasi, &ctx.node_id);
+    }
+    if self.mirror_stdio {
+      // `initial_state` runs inside the actor's instrumented task. Both
+      // streams hold a handle to that span (cheap refcount clone).
+      let span = tracing::Span::current();
+      wasi.stdout(TracingStdio {
+        stream: "stdout",
+        span: span.clone(),
+      });
+      wasi.stderr(TracingStdio {
+        stream: "stderr",
+        span,
+      });
+    }
     DefaultHostState {
-      wasi: WasiCtxBuilder::new().build(),
+      wasi: wasi.build(),
       table: ResourceTable::new(),
       http: Arc::clone(&self.http),
       emitter,
@@ -229,3 +483,92 @@ fn from_payload(p: Payload) -> Result<Message, String> {
     value: MessageValue::Binary(p.value),
   })
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::cell::RefCell;
+  use std::collections::HashSet;
+  use std::rc::Rc;
+
+  fn stream(seed: u64, len: usize) -> Vec<u8> {
+    let mut buf = vec![0; len];
+    SplitMix64(seed).fill_bytes(&mut buf);
+    buf
+  }
+
+  #[test]
+  fn seeded_random_is_reproducible_and_seed_specific() {
+    assert_eq!(stream(7, 4096), stream(7, 4096));
+    assert_ne!(stream(7, 4096), stream(8, 4096));
+    assert_ne!(stream(7, 4096), stream(!7, 4096));
+  }
+
+  #[test]
+  fn seeded_random_does_not_cycle() {
+    let bytes = stream(42, 64 * 1024);
+    // The old 1 KiB byte cycle repeated here.
+    assert_ne!(bytes[..1024], bytes[1024..2048]);
+    let words: HashSet<&[u8]> = bytes.chunks(8).collect();
+    assert_eq!(words.len(), bytes.len() / 8);
+  }
+
+  /// Writes each chunk through a fresh `LineWriter`, dropped at the end.
+  fn lines(chunks: &[&[u8]]) -> Vec<String> {
+    let out = Rc::new(RefCell::new(Vec::new()));
+    let sink = Rc::clone(&out);
+    let mut writer = LineWriter::new(move |text: &str| sink.borrow_mut().push(text.to_string()));
+    let mut cx = std::task::Context::from_waker(futures::task::noop_waker_ref());
+    for chunk in chunks {
+      let written = Pin::new(&mut writer).poll_write(&mut cx, chunk);
+      assert!(matches!(written, Poll::Ready(Ok(n)) if n == chunk.len()));
+    }
+    drop(writer);
+    out.take()
+  }
+
+  #[test]
+  fn line_writer_splits_lines_across_writes() {
+    assert_eq!(lines(&[b"a\nb", b"c\n\nd\n"]), ["a", "bc", "d"]);
+  }
+
+  #[test]
+  fn line_writer_flushes_partial_line_on_drop() {
+    assert_eq!(lines(&[b"done\nno newline"]), ["done", "no newline"]);
+  }
+
+  #[test]
+  fn line_writer_cuts_long_lines() {
+    let long = vec![b'x'; MAX_STDIO_LINE + 3];
+    let got = lines(&[&long]);
+    assert_eq!(got.len(), 2);
+    assert_eq!(got[0].len(), MAX_STDIO_LINE);
+    assert_eq!(got[1], "xxx");
+  }
+
+  #[test]
+  fn line_writer_cuts_at_char_boundary() {
+    // The cut falls one byte into the two-byte `é`, which moves whole to
+    // the next line.
+    let mut long = vec![b'x'; MAX_STDIO_LINE - 1];
+    long.extend_from_slice("éz".as_bytes());
+    let got = lines(&[&long]);
+    assert_eq!(got.len(), 2);
+    assert_eq!(got[0].len(), MAX_STDIO_LINE - 1);
+    assert_eq!(got[1], "éz");
+  }
+
+  #[test]
+  fn seeded_random_differs_per_actor() {
+    let host = Determinism {
+      seed: 7,
+      start: Duration::ZERO,
+    };
+    let node = |id: &str| stream(host.actor_seed(id), 4096);
+    assert_eq!(node("parse"), node("parse"));
+    assert_ne!(node("parse"), node("enrich"));
+    // Ids that differ only in trailing zero bytes or in length.
+    assert_ne!(node("a"), node("a\0"));
+    assert_ne!(node(""), node("\0"));
+  }
+}
@@ -0,0 +1,176 @@
+use fuchsia_actor::ActorError;
+use wasmtime::{Cache, Config, Engine, OptLevel, PoolingAllocationConfig};
+
+/// Linear-memory reservation used by [`EngineProfile::LowMemory`].
+const LOW_MEMORY_RESERVATION: u64 = 64 * 1024 * 1024;
+
+/// Guard region used by [`EngineProfile::LowMemory`].
+const LOW_MEMORY_GUARD: u64 = 64 * 1024;
+
+/// Live component instances (one per wasm node) in the
+/// [`EngineProfile::LowLatency`] pool; also its async stack count.
+pub const LOW_LATENCY_INSTANCES: u32 = 128;
+
+/// Core instances, memories and tables per component in the
+/// [`EngineProfile::LowLatency`] pool. Covers a module plus the WASI
+/// adapter and its shims.
+const LOW_LATENCY_CORE_PER_INSTANCE: u32 = 4;
+
+/// Largest linear memory in the [`EngineProfile::LowLatency`] pool, and
+/// each memory slot's reservation.
+pub const LOW_LATENCY_MEMORY: u64 = 64 * 1024 * 1024;
+
+/// Guard region after each [`EngineProfile::LowLatency`] memory slot.
+const LOW_LATENCY_GUARD: u64 = 64 * 1024;
+
+/// Preset wasmtime tuning for a deployment.
+///
+/// Every profile enables async support and the component model, which
+/// `WasmActor` requires. Convert a profile into an [`EngineConfig`] to
+/// adjust individual knobs, or call [`config`](Self::config) to get the
+/// `wasmtime::Config` and layer further settings on top —
+/// `epoch_interruption`, fuel — before building the engine.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EngineProfile {
+  /// Wasmtime's defaults: code optimized for speed, parallel compilation,
+  /// and on-demand instance allocation with no cap on concurrent actors.
+  #[default]
+  Throughput,
+  /// Like `Throughput`, but instances come from the pooling allocator's
+  /// preallocated slots, making workflow start (one instantiation per node)
+  /// cheap. The pool holds [`LOW_LATENCY_INSTANCES`] components with up to
+  /// four core instances and memories each; instantiating past that fails.
+  /// Memories cannot grow past [`LOW_LATENCY_MEMORY`], so the pool reserves
+  /// about 32 GiB of virtual address space up front (not resident memory).
+  /// Bounds checks are not elided.
+  LowLatency,
+  /// Optimize for code size, compile serially, and shrink each linear
+  /// memory's virtual reservation and guard region. Memories that outgrow
+  /// the reservation are moved, and bounds checks are no longer elided.
+  LowMemory,
+}
+
+impl EngineProfile {
+  /// The `wasmtime::Config` for this profile.
+  pub fn config(self) -> Config {
+    EngineConfig::from(self).config()
+  }
+
+  /// Build an `Engine` straight from this profile.
+  pub fn engine(self) -> Result<Engine, ActorError> {
+    EngineConfig::from(self).engine()
+  }
+}
+
+/// Wasmtime knobs, starting from an [`EngineProfile`] preset.
+///
+/// ```ignore
+/// let engine = EngineConfig::from(EngineProfile::LowLatency)
+///   .native_unwind_info(false)
+///   .cache(Some(wasmtime::Cache::from_file(None)?))
+///   .engine()?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct EngineConfig {
+  opt_level: OptLevel,
+  parallel_compilation: bool,
+  pooling: Option<PoolingAllocationConfig>,
+  memory: Option<(u64, u64)>,
+  native_unwind_info: bool,
+  cache: Option<Cache>,
+}
+
+impl From<EngineProfile> for EngineConfig {
+  fn from(profile: EngineProfile) -> Self {
+    let mut config = Self {
+      opt_level: OptLevel::Speed,
+      parallel_compilation: true,
+      pooling: None,
+      memory: None,
+      native_unwind_info: true,
+      cache: None,
+    };
+    match profile {
+      EngineProfile::Throughput => {}
+      EngineProfile::LowLatency => {
+        let core = LOW_LATENCY_INSTANCES * LOW_LATENCY_CORE_PER_INSTANCE;
+        let mut pooling = PoolingAllocationConfig::new();
+        pooling
+          .total_component_instances(LOW_LATENCY_INSTANCES)
+          .total_stacks(LOW_LATENCY_INSTANCES)
+          .total_core_instances(core)
+          .total_memories(core)
+          .total_tables(core)
+          .max_memory_size(usize::try_from(LOW_LATENCY_MEMORY).unwrap_or(usize::MAX));
+        config.pooling = Some(pooling);
+        // A pool slot must cover the largest memory it can hold.
+        config.memory = Some((LOW_LATENCY_MEMORY, LOW_LATENCY_GUARD));
+      }
+      EngineProfile::LowMemory => {
+        config.opt_level = OptLevel::SpeedAndSize;
+        config.parallel_compilation = false;
+        config.memory = Some((LOW_MEMORY_RESERVATION, LOW_MEMORY_GUARD));
+      }
+    }
+    config
+  }
+}
+
+impl EngineConfig {
+  /// Cranelift optimization level.
+  pub fn opt_level(mut self, level: OptLevel) -> Self {
+    self.opt_level = level;
+    self
+  }
+
+  /// Compile functions on multiple threads.
+  pub fn parallel_compilation(mut self, enable: bool) -> Self {
+    self.parallel_compilation = enable;
+    self
+  }
+
+  /// Allocate instances from a preallocated pool, or on demand with `None`.
+  pub fn pooling(mut self, pooling: Option<PoolingAllocationConfig>) -> Self {
+    self.pooling = pooling;
+    self
+  }
+
+  /// Emit native unwind info (`.eh_frame`) for compiled code. On by
+  /// default; hosts loading many components may turn it off to skip its
+  /// registration cost. Wasm backtraces do not depend on it.
+  pub fn native_unwind_info(mut self, enable: bool) -> Self {
+    self.native_unwind_info = enable;
+    self
+  }
+
+  /// On-disk compilation cache. Off by default.
+  pub fn cache(mut self, cache: Option<Cache>) -> Self {
+    self.cache = cache;
+    self
+  }
+
+  /// The `wasmtime::Config` for these settings.
+  pub fn config(self) -> Config {
+    let mut config = Config::new();
+    config.async_support(true);
+    config.wasm_component_model(true);
+    config.cranelift_opt_level(self.opt_level);
+    config.parallel_compilation(self.parallel_compilation);
+    config.native_unwind_info(self.native_unwind_info);
+    config.cache(self.cache);
+    if let Some(pooling) = self.pooling {
+      config.allocation_strategy(pooling);
+    }
+    if let Some((reservation, guard)) = self.memory {
+      config.memory_reservation(reservation);
+      config.memory_guard_size(guard);
+    }
+    config
+  }
+
+  /// Build an `Engine` from these settings.
+  pub fn engine(self) -> Result<Engine, ActorError> {
+    Engine::new(&self.config())
+      .map_err(|e| ActorError::Other(format!("create wasmtime engine: {e}")))
+  }
+}
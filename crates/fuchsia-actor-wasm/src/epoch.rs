@@ -0,0 +1,62 @@
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, MissedTickBehavior};
+use wasmtime::Engine;
+
+/// Shortest interval [`EpochTicker::spawn`] accepts; shorter requests are
+/// raised to this.
+const MIN_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Drives an engine's epoch from a tokio task, so `epoch_deadline` budgets
+/// turn into wall-clock timeouts.
+///
+/// The engine must be built with `Config::epoch_interruption(true)`. Convert
+/// a time budget to ticks with [`ticks_for`](Self::ticks_for) instead of
+/// assuming a tick rate, so the deadline follows the ticker's real interval.
+/// A call then traps between `budget - interval` and `budget + interval`
+/// after it starts, depending on where it falls between ticks. The task is
+/// aborted when the ticker is dropped.
+pub struct EpochTicker {
+  interval: Duration,
+  task: JoinHandle<()>,
+}
+
+impl EpochTicker {
+  /// Start ticking `engine` every `interval` (at least 1 ms). Must be
+  /// called from within a tokio runtime. Ticks missed while the runtime was
+  /// busy are skipped, not replayed, so a stall never makes calls time out
+  /// early.
+  pub fn spawn(engine: &Engine, interval: Duration) -> Self {
+    let interval = interval.max(MIN_INTERVAL);
+    // Engine is an Arc handle; the task owns one for its lifetime.
+    let engine = engine.clone();
+    let task = tokio::spawn(async move {
+      // interval_at: the first tick comes one interval in, not immediately.
+      let mut ticks = tokio::time::interval_at(Instant::now() + interval, interval);
+      ticks.set_missed_tick_behavior(MissedTickBehavior::Skip);
+      loop {
+        ticks.tick().await;
+        engine.increment_epoch();
+      }
+    });
+    Self { interval, task }
+  }
+
+  /// The interval between epoch increments.
+  pub fn interval(&self) -> Duration {
+    self.interval
+  }
+
+  /// Ticks covering `budget`, rounded up, for
+  /// [`WasmActorBuilder::epoch_deadline`](crate::WasmActorBuilder::epoch_deadline).
+  pub fn ticks_for(&self, budget: Duration) -> u64 {
+    let ticks = budget.as_nanos().div_ceil(self.interval.as_nanos());
+    u64::try_from(ticks).unwrap_or(u64::MAX)
+  }
+}
+
+impl Drop for EpochTicker {
+  fn drop(&mut self) {
+    self.task.abort();
+  }
+}
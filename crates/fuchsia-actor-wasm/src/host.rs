@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 use fuchsia_actor::{Context, Emitter, Message};
 use wasmtime::Store;
-use wasmtime::component::{Component, Linker};
+use wasmtime::component::{Component, InstancePre, Linker};
 use wasmtime_wasi::WasiView;
 
 /// Glue between [`WasmActor`](crate::WasmActor) and a host-specific wasm world.
@@ -45,10 +45,21 @@ pub trait WasmHost: 'static + Send + Sync {
   /// the `fuchsia:actor/emit` import alongside any other host imports.
   fn add_to_linker(&self, linker: &mut Linker<Self::State>) -> wasmtime::Result<()>;
 
+  /// Check that the component exports what this host's trampolines call.
+  /// Called once at builder time, after `pre` has passed import
+  /// type-checking against the linker, so a mismatched component fails in
+  /// `build()` rather than when its workflow starts. Typically
+  /// `<World>Pre::new(pre.clone())` from the bindgen output. Defaults to
+  /// accepting any component.
+  fn check_exports(&self, _pre: &InstancePre<Self::State>) -> wasmtime::Result<()> {
+    Ok(())
+  }
+
   /// Build the per-actor `State`. Called once when the actor starts running.
   /// The provided `Emitter` is the actor's outbound channel — implementations
-  /// must store it where the emit import callback can find it.
-  fn initial_state(&self, emitter: Emitter) -> Self::State;
+  /// must store it where the emit import callback can find it. `ctx`
+  /// identifies the node, for state that must differ between actors.
+  fn initial_state(&self, emitter: Emitter, ctx: &Context) -> Self::State;
 
   /// Instantiate the component into the store using the (pre-built) linker.
   /// Called once at the top of the actor's run loop. The returned bindings
@@ -11,9 +11,13 @@
 mod actor;
 mod builder;
 mod default;
+mod engine;
+mod epoch;
 mod host;
 
 pub use actor::WasmActor;
 pub use builder::WasmActorBuilder;
-pub use default::{DefaultHost, DefaultHostState};
+pub use default::{DefaultHost, DefaultHostState, Determinism, MAX_STDIO_LINE};
+pub use engine::{EngineConfig, EngineProfile, LOW_LATENCY_INSTANCES, LOW_LATENCY_MEMORY};
+pub use epoch::EpochTicker;
 pub use host::WasmHost;
@@ -7,18 +7,68 @@
 
 use async_trait::async_trait;
 use fuchsia_actor::{Actor, ActorError, Context, Emitter, Inbox, Message, MessageValue};
-use fuchsia_actor_wasm::{DefaultHost, WasmActor};
+use fuchsia_actor_wasm::{
+  DefaultHost, EngineConfig, EngineProfile, EpochTicker, LOW_LATENCY_MEMORY, WasmActor,
+};
 use fuchsia_capabilities::http::{AllowedHosts, ReqwestHttp};
-use fuchsia_runtime::{ActorRegistry, Edge, Graph, Node, Orchestrator};
+use fuchsia_runtime::{ActorRegistry, Edge, EdgeKind, Graph, Node, Orchestrator};
 use serde_json::{Value, json};
 use std::path::Path;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 const TEST_WASM: &str = concat!(
   env!("CARGO_MANIFEST_DIR"),
   "/../../test-components/test-actor-component/target/wasm32-wasip1/release/test_actor_component.wasm"
 );
 
+/// Component whose `handle` spins forever.
+const SPIN_COMPONENT: &str = r#"
+  (component
+    (core module $m
+      (memory (export "memory") 1)
+      (func (export "realloc") (param i32 i32 i32 i32) (result i32)
+        i32.const 64)
+      ;; Results are returned through memory; offset 0 holds zeroes, which
+      ;; reads back as `ok`.
+      (func (export "setup") (param i32 i32 i32 i32 i32 i32) (result i32)
+        i32.const 0)
+      (func (export "handle")
+        (param i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32) (result i32)
+        (loop $spin (br $spin))
+        i32.const 0)
+      (func (export "teardown") (param i32 i32 i32 i32 i32 i32) (result i32)
+        i32.const 0))
+    (core instance $i (instantiate $m))
+
+    (type $context (record
+      (field "execution-id" string)
+      (field "node-id" string)
+      (field "task-id" string)))
+    (type $payload (record
+      (field "type" string)
+      (field "correlation-id" (option string))
+      (field "value" (list u8))))
+    (type $result (result (error string)))
+    (func $setup (param "ctx" $context) (result $result)
+      (canon lift (core func $i "setup")
+        (memory $i "memory") (realloc (func $i "realloc"))))
+    (func $handle (param "ctx" $context) (param "msg" $payload) (result $result)
+      (canon lift (core func $i "handle")
+        (memory $i "memory") (realloc (func $i "realloc"))))
+    (func $teardown (param "ctx" $context) (result $result)
+      (canon lift (core func $i "teardown")
+        (memory $i "memory") (realloc (func $i "realloc"))))
+
+    (instance $actor
+      (export "context" (type $context))
+      (export "payload" (type $payload))
+      (export "setup" (func $setup))
+      (export "handle" (func $handle))
+      (export "teardown" (func $teardown)))
+    (export "fuchsia:actor/actor@0.1.0" (instance $actor)))
+"#;
+
 struct Recorder {
   out: Arc<Mutex<Vec<Message>>>,
 }
@@ -95,6 +145,7 @@ async fn wasm_actor_runs_test_component_end_to_end() {
     edges: vec![Edge {
       from: "wasm".into(),
       to: "rec".into(),
+      kind: EdgeKind::Data,
     }],
   };
 
@@ -122,3 +173,139 @@ async fn wasm_actor_runs_test_component_end_to_end() {
   assert_eq!(v["echoed"], json!(42));
   assert_eq!(v["node"], json!("wasm"));
 }
+
+#[test]
+fn build_rejects_component_without_actor_exports() {
+  let engine = EngineProfile::default()
+    .engine()
+    .expect("create wasmtime engine");
+  let http = Arc::new(ReqwestHttp::new(AllowedHosts::default()));
+
+  let built = WasmActor::builder(engine, DefaultHost::new(http))
+    .component_from_bytes(b"(component)".to_vec())
+    .build();
+
+  assert!(
+    matches!(&built, Err(ActorError::Other(m)) if m.starts_with("component exports:")),
+    "expected export preflight failure"
+  );
+}
+
+#[test]
+fn every_engine_profile_builds() {
+  for profile in [
+    EngineProfile::Throughput,
+    EngineProfile::LowLatency,
+    EngineProfile::LowMemory,
+  ] {
+    let engine = profile
+      .engine()
+      .unwrap_or_else(|e| panic!("{profile:?}: {e}"));
+    // Compiling proves the profile's settings are accepted by Cranelift,
+    // not just by `Engine::new`.
+    wasmtime::component::Component::new(&engine, b"(component)")
+      .unwrap_or_else(|e| panic!("{profile:?}: {e}"));
+  }
+
+  EngineConfig::from(EngineProfile::LowLatency)
+    .native_unwind_info(false)
+    .parallel_compilation(false)
+    .engine()
+    .expect("create tuned wasmtime engine");
+}
+
+#[tokio::test]
+async fn low_latency_caps_linear_memory() {
+  let engine = EngineProfile::LowLatency
+    .engine()
+    .expect("create wasmtime engine");
+  let module = |pages: u64| wasmtime::Module::new(&engine, format!("(module (memory {pages}))"));
+  let pages = LOW_LATENCY_MEMORY / 65536;
+
+  let fits = module(pages).expect("compile module");
+  let mut store = wasmtime::Store::new(&engine, ());
+  wasmtime::Instance::new_async(&mut store, &fits, &[])
+    .await
+    .expect("instantiate module at the cap");
+  assert!(module(pages + 1).is_err(), "memory past the cap compiled");
+}
+
+#[tokio::test]
+async fn ticks_for_rounds_budget_up_to_one_tick() {
+  let mut config = EngineProfile::default().config();
+  config.epoch_interruption(true);
+  let engine = wasmtime::Engine::new(&config).expect("create wasmtime engine");
+
+  for interval_ms in [1, 7, 10, 250] {
+    let ticker = EpochTicker::spawn(&engine, Duration::from_millis(interval_ms));
+    let interval = ticker.interval();
+    for budget_ms in [1, 10, 99, 100, 1_000, 30_000] {
+      let budget = Duration::from_millis(budget_ms);
+      let covered = interval * u32::try_from(ticker.ticks_for(budget)).unwrap();
+      assert!(
+        covered >= budget,
+        "{interval:?} ticks undershoot {budget:?}"
+      );
+      assert!(
+        covered < budget + interval,
+        "{interval:?} ticks overshoot {budget:?}"
+      );
+    }
+  }
+
+  let ticker = EpochTicker::spawn(&engine, Duration::ZERO);
+  assert_eq!(ticker.interval(), Duration::from_millis(1));
+  assert_eq!(ticker.ticks_for(Duration::ZERO), 0);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn epoch_deadline_traps_looping_handle() {
+  let mut config = EngineProfile::default().config();
+  config.epoch_interruption(true);
+  let engine = wasmtime::Engine::new(&config).expect("create wasmtime engine");
+  // The guest pins one worker thread; the ticker runs on the other.
+  let ticker = EpochTicker::spawn(&engine, Duration::from_millis(10));
+  let budget = Duration::from_millis(200);
+
+  let http = Arc::new(ReqwestHttp::new(AllowedHosts::default()));
+  let actor = WasmActor::builder(engine, DefaultHost::new(http))
+    .component_from_bytes(SPIN_COMPONENT.as_bytes().to_vec())
+    .epoch_deadline(ticker.ticks_for(budget))
+    .build()
+    .expect("build WasmActor");
+
+  let mut registry = ActorRegistry::new();
+  registry.register::<WasmActor<DefaultHost>, Value, _>("spin.wasm", move |_| actor.clone());
+  let graph = Graph {
+    entry: "spin".into(),
+    nodes: vec![Node {
+      id: "spin".into(),
+      actor: "spin.wasm".into(),
+      config: Value::Null,
+    }],
+    edges: vec![],
+  };
+  let handle = Orchestrator::new(Arc::new(registry))
+    .start(&graph)
+    .expect("start workflow");
+
+  let started = std::time::Instant::now();
+  handle
+    .send(Message::with_type("spin").json(json!(1)))
+    .await
+    .expect("send input");
+  let results = handle.join().await;
+  let elapsed = started.elapsed();
+
+  assert!(
+    matches!(&results[0], Err(ActorError::Other(m)) if m.starts_with("wasm trap (handle):")),
+    "expected an epoch trap, got {results:?}"
+  );
+  let interval = ticker.interval();
+  assert!(elapsed >= budget - interval, "trapped early: {elapsed:?}");
+  // Past `budget + interval`, allow for instantiation and scheduling.
+  assert!(
+    elapsed < budget + interval + Duration::from_millis(250),
+    "trapped late: {elapsed:?}"
+  );
+}
@@ -0,0 +1,34 @@
+use serde_json::Value;
+
+/// Append `value` to `out` with object keys in sorted order, independent of
+/// whether `serde_json` was built with `preserve_order`. Keys and strings
+/// are length-prefixed, so equal bytes mean equal values.
+pub fn write_canonical(out: &mut Vec<u8>, value: &Value) {
+  match value {
+    Value::Object(map) => {
+      let mut keys: Vec<&String> = map.keys().collect();
+      keys.sort();
+      out.push(b'{');
+      for key in keys {
+        write_field(out, "key", key);
+        write_canonical(out, &map[key]);
+      }
+      out.push(b'}');
+    }
+    Value::Array(items) => {
+      out.push(b'[');
+      for item in items {
+        write_canonical(out, item);
+      }
+      out.push(b']');
+    }
+    Value::String(s) => write_field(out, "str", s),
+    other => write_field(out, "lit", &other.to_string()),
+  }
+}
+
+fn write_field(out: &mut Vec<u8>, name: &str, value: &str) {
+  out.extend_from_slice(name.as_bytes());
+  out.extend_from_slice(&(value.len() as u64).to_le_bytes());
+  out.extend_from_slice(value.as_bytes());
+}
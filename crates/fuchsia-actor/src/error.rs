@@ -1,3 +1,7 @@
+use std::ffi::OsStr;
+use std::fmt::Display;
+use std::io;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -8,15 +12,75 @@ pub enum ActorError {
   #[error("unknown node referenced by graph: {0}")]
   UnknownNode(String),
 
+  #[error("actor not allowed by policy: {0}")]
+  NotAllowed(String),
+
   #[error("failed to deserialize actor config: {0}")]
   Config(#[from] serde_json::Error),
 
+  #[error("load {}: {reason}", path.display())]
+  Load { path: PathBuf, reason: String },
+
   #[error("channel send failed: {0}")]
   Send(String),
 
+  #[error("channel full")]
+  Full,
+
   #[error("actor task panicked")]
   Panic,
 
+  #[error("actor task aborted")]
+  Aborted,
+
   #[error("{0}")]
   Other(String),
 }
+
+impl ActorError {
+  /// A [`ActorError::Load`] for `path`. When the file is missing, the reason
+  /// also says what is on disk: either the parent directory is missing too, or
+  /// it lists the files in it that share the extension. A wrong version or a
+  /// typo in a file name is then visible in the error.
+  pub fn load(path: &Path, err: impl Display) -> Self {
+    // Only a definite "not there" earns the hint; if existence can't be
+    // checked, `err` already says why.
+    let reason = match path.try_exists() {
+      Ok(false) => format!("{err}; {}", missing_file_hint(path)),
+      Ok(true) | Err(_) => err.to_string(),
+    };
+    Self::Load {
+      path: path.to_path_buf(),
+      reason,
+    }
+  }
+}
+
+fn missing_file_hint(path: &Path) -> String {
+  let dir = match path.parent() {
+    Some(p) if !p.as_os_str().is_empty() => p,
+    _ => Path::new("."),
+  };
+  match siblings(dir, path.extension()) {
+    Ok(siblings) if siblings.is_empty() => format!("no matching files in {}", dir.display()),
+    Ok(siblings) => format!("{} contains: {}", dir.display(), siblings.join(", ")),
+    Err(e) if e.kind() == io::ErrorKind::NotFound => {
+      format!("directory {} does not exist", dir.display())
+    }
+    Err(e) => format!("cannot list {}: {e}", dir.display()),
+  }
+}
+
+/// Sorted names of the non-directory entries in `dir` with extension `ext`.
+fn siblings(dir: &Path, ext: Option<&OsStr>) -> io::Result<Vec<String>> {
+  let mut names = Vec::new();
+  for entry in std::fs::read_dir(dir)? {
+    let entry = entry?;
+    let name = PathBuf::from(entry.file_name());
+    if name.extension() == ext && !entry.file_type()?.is_dir() {
+      names.push(name.to_string_lossy().into_owned());
+    }
+  }
+  names.sort();
+  Ok(names)
+}
@@ -1,9 +1,11 @@
 pub mod actor;
+pub mod canonical;
 pub mod channel;
 pub mod context;
 pub mod error;
 
 pub use actor::Actor;
+pub use canonical::write_canonical;
 pub use channel::{Emitter, Inbox, Message, MessageBuilder, MessageValue};
 pub use context::Context;
 pub use error::ActorError;
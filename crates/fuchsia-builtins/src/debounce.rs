@@ -0,0 +1,118 @@
+use crate::template::{describe, environment, render};
+use async_trait::async_trait;
+use fuchsia_actor::{Actor, ActorError, Context, Emitter, Inbox, Message};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Per-node configuration for [`DebounceActor`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct DebounceConfig {
+  /// Quiet period after the last message for a key before it is emitted.
+  pub window_ms: u64,
+  /// minijinja template rendering the debounce key (same context as
+  /// [`TransformActor`](crate::TransformActor)). When unset, every message
+  /// shares one key.
+  #[serde(default)]
+  pub key: Option<String>,
+  /// Fail when `key` touches an undefined variable or attribute, instead
+  /// of rendering it empty.
+  #[serde(default)]
+  pub strict: bool,
+  /// Most keys held pending at once. A message for a new key beyond this
+  /// emits the pending message closest to its deadline early, so
+  /// high-cardinality keys cost bounded memory. At least 1.
+  #[serde(default = "default_max_keys")]
+  pub max_keys: usize,
+}
+
+fn default_max_keys() -> usize {
+  10_000
+}
+
+/// Native actor that collapses bursts of messages per key into the last
+/// message of the burst, emitted once the key has been quiet for
+/// `window_ms`. Useful in front of expensive branches fed by file-watch or
+/// webhook storms.
+///
+/// Keys are independent: a burst on one key never delays another. When the
+/// inbox closes, pending messages are flushed in deadline order. At most
+/// `max_keys` keys are pending; past that the oldest is emitted early.
+pub struct DebounceActor {
+  config: DebounceConfig,
+}
+
+impl DebounceActor {
+  pub fn new(config: DebounceConfig) -> Self {
+    Self { config }
+  }
+}
+
+#[async_trait]
+impl Actor for DebounceActor {
+  async fn run(&self, mut inbox: Inbox, emit: Emitter, ctx: Context) -> Result<(), ActorError> {
+    let env = environment(self.config.strict);
+    let key_template = match &self.config.key {
+      Some(source) => Some(
+        env
+          .template_from_named_str("key", source)
+          .map_err(|e| ActorError::Other(format!("debounce key template: {}", describe(&e))))?,
+      ),
+      None => None,
+    };
+    let window = Duration::from_millis(self.config.window_ms);
+    let max_keys = self.config.max_keys.max(1);
+    let mut pending: HashMap<String, (Message, Instant)> = HashMap::new();
+
+    loop {
+      let next_deadline = pending.values().map(|(_, deadline)| *deadline).min();
+
+      tokio::select! {
+          _ = ctx.cancelled() => return Ok(()),
+          msg = inbox.recv() => match msg {
+              Some(msg) => {
+                  let key = match &key_template {
+                      Some(template) => render(template, &msg)?,
+                      None => String::new(),
+                  };
+                  if pending.len() >= max_keys && !pending.contains_key(&key) {
+                      let oldest = pending
+                          .iter()
+                          .min_by_key(|(_, (_, deadline))| *deadline)
+                          // Owned so the map can be mutated below.
+                          .map(|(key, _)| key.clone());
+                      if let Some((early, _)) = oldest.and_then(|key| pending.remove(&key)) {
+                          emit.send(early).await?;
+                      }
+                  }
+                  pending.insert(key, (msg, Instant::now() + window));
+              }
+              None => {
+                  let mut remaining: Vec<_> = pending.into_values().collect();
+                  remaining.sort_by_key(|(_, deadline)| *deadline);
+                  for (msg, _) in remaining {
+                      emit.send(msg).await?;
+                  }
+                  return Ok(());
+              }
+          },
+          _ = async {
+              if let Some(deadline) = next_deadline {
+                  tokio::time::sleep_until(deadline).await;
+              }
+          }, if next_deadline.is_some() => {
+              let now = Instant::now();
+              let (mut due, waiting): (Vec<_>, Vec<_>) = pending
+                  .drain()
+                  .partition(|(_, (_, deadline))| *deadline <= now);
+              pending.extend(waiting);
+              due.sort_by_key(|(_, (_, deadline))| *deadline);
+              for (_, (msg, _)) in due {
+                  emit.send(msg).await?;
+              }
+          }
+      }
+    }
+  }
+}
@@ -0,0 +1,111 @@
+use crate::template::{describe, environment, render};
+use async_trait::async_trait;
+use fuchsia_actor::{
+  Actor, ActorError, Context, Emitter, Inbox, Message, MessageValue, write_canonical,
+};
+use serde::Deserialize;
+use std::collections::{HashSet, VecDeque};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Per-node configuration for [`DedupActor`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct DedupConfig {
+  /// How long a fingerprint suppresses repeats after it is first seen.
+  /// Fingerprints are held in full for the window, so set `key` when
+  /// payloads are large.
+  pub window_ms: u64,
+  /// minijinja template rendering the fingerprint (same context as
+  /// [`TransformActor`](crate::TransformActor)). When unset, the message
+  /// type and full serialized value are the fingerprint.
+  #[serde(default)]
+  pub key: Option<String>,
+  /// Fail when `key` touches an undefined variable or attribute, instead
+  /// of rendering it empty.
+  #[serde(default)]
+  pub strict: bool,
+}
+
+/// Native actor that forwards the first message per fingerprint and drops
+/// repeats seen within `window_ms` of it. Useful behind at-least-once
+/// sources (webhook retries, overlapping polls).
+///
+/// The window is in-memory and per actor instance; a restarted workflow
+/// starts with an empty window.
+pub struct DedupActor {
+  config: DedupConfig,
+}
+
+impl DedupActor {
+  pub fn new(config: DedupConfig) -> Self {
+    Self { config }
+  }
+}
+
+/// Exact identity of a message when no `key` is configured: the type and
+/// the value, length-prefixed so the two can't run together. JSON is
+/// encoded canonically, so key order doesn't matter.
+fn value_fingerprint(msg: &Message) -> Vec<u8> {
+  let mut fingerprint = Vec::new();
+  fingerprint.extend_from_slice(&msg.type_.len().to_le_bytes());
+  fingerprint.extend_from_slice(msg.type_.as_bytes());
+  match &msg.value {
+    MessageValue::Json(v) => write_canonical(&mut fingerprint, v),
+    MessageValue::Binary(b) => fingerprint.extend_from_slice(b),
+    MessageValue::Empty => {}
+  }
+  fingerprint
+}
+
+#[async_trait]
+impl Actor for DedupActor {
+  async fn run(&self, mut inbox: Inbox, emit: Emitter, ctx: Context) -> Result<(), ActorError> {
+    let env = environment(self.config.strict);
+    let key_template = match &self.config.key {
+      Some(source) => Some(
+        env
+          .template_from_named_str("key", source)
+          .map_err(|e| ActorError::Other(format!("dedup key template: {}", describe(&e))))?,
+      ),
+      None => None,
+    };
+    let window = Duration::from_millis(self.config.window_ms);
+    // Fingerprints are compared exactly, never hashed, so distinct messages
+    // can't collide. Windows are equal length, so insertion order is expiry
+    // order and pruning only pops from the front.
+    let mut seen: HashSet<Vec<u8>> = HashSet::new();
+    let mut expiries: VecDeque<(Instant, Vec<u8>)> = VecDeque::new();
+
+    loop {
+      let msg = tokio::select! {
+        _ = ctx.cancelled() => return Ok(()),
+        msg = inbox.recv() => msg,
+      };
+      let Some(msg) = msg else {
+        return Ok(());
+      };
+
+      let fingerprint = match &key_template {
+        Some(template) => render(template, &msg)?.into_bytes(),
+        None => value_fingerprint(&msg),
+      };
+
+      let now = Instant::now();
+      while let Some((expires, _)) = expiries.front()
+        && *expires <= now
+      {
+        if let Some((_, expired)) = expiries.pop_front() {
+          seen.remove(&expired);
+        }
+      }
+      if seen.contains(&fingerprint) {
+        tracing::debug!("duplicate suppressed");
+        continue;
+      }
+      // One copy for membership, one for the expiry queue.
+      seen.insert(fingerprint.clone());
+      expiries.push_back((now + window, fingerprint));
+      emit.send(msg).await?;
+    }
+  }
+}
@@ -0,0 +1,69 @@
+use crate::template::{context, describe, environment};
+use async_trait::async_trait;
+use fuchsia_actor::{Actor, ActorError, Context, Emitter, Inbox};
+use serde::Deserialize;
+
+/// Per-node configuration for [`FilterActor`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct FilterConfig {
+  /// minijinja expression (same context as
+  /// [`TransformActor`](crate::TransformActor)), e.g.
+  /// `value.status >= 500`. Messages are forwarded when it is truthy.
+  pub when: String,
+  /// Fail when `when` touches an undefined variable or attribute, instead
+  /// of treating it as falsy.
+  #[serde(default)]
+  pub strict: bool,
+}
+
+/// Native actor that forwards inbound messages unchanged when the `when`
+/// expression is truthy and drops them otherwise. This is the graph's
+/// edge-condition primitive: put it between two nodes to make that hop
+/// conditional.
+///
+/// The expression is compiled once when the actor starts; a compile or
+/// evaluation error ends the actor.
+pub struct FilterActor {
+  config: FilterConfig,
+}
+
+impl FilterActor {
+  pub fn new(config: FilterConfig) -> Self {
+    Self { config }
+  }
+}
+
+#[async_trait]
+impl Actor for FilterActor {
+  async fn run(&self, mut inbox: Inbox, emit: Emitter, ctx: Context) -> Result<(), ActorError> {
+    let env = environment(self.config.strict);
+    let when = env
+      .compile_expression(&self.config.when)
+      .map_err(|e| ActorError::Other(format!("filter expression: {}", describe(&e))))?;
+
+    loop {
+      let msg = tokio::select! {
+        _ = ctx.cancelled() => return Ok(()),
+        msg = inbox.recv() => msg,
+      };
+      let Some(msg) = msg else {
+        return Ok(());
+      };
+
+      let result = when
+        .eval(context(&msg)?)
+        .map_err(|e| ActorError::Other(format!("filter expression: {}", describe(&e))))?;
+      // Strict mode only errors when an undefined value is used; a bare
+      // `value.flag` evaluates to undefined without complaint.
+      if self.config.strict && result.is_undefined() {
+        return Err(ActorError::Other(format!(
+          "filter expression: `{}` is undefined",
+          self.config.when
+        )));
+      }
+      if result.is_true() {
+        emit.send(msg).await?;
+      }
+    }
+  }
+}
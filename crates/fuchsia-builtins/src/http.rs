@@ -0,0 +1,264 @@
+use crate::template::{describe, environment, render};
+use async_trait::async_trait;
+use fuchsia_actor::{Actor, ActorError, Context, Emitter, Inbox, Message, MessageValue};
+use fuchsia_capabilities::http::{HttpClient, HttpError, HttpRequest, HttpResponse};
+use minijinja::{Environment, Template};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Per-node configuration for [`HttpActor`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct HttpConfig {
+  #[serde(default = "default_method")]
+  pub method: String,
+  /// minijinja template (same context as
+  /// [`TransformActor`](crate::TransformActor)), rendered per message, e.g.
+  /// `https://api.example.com/items/{{ value.id }}`.
+  pub url: String,
+  /// Header values are templates, like `url`.
+  #[serde(default)]
+  pub headers: HashMap<String, String>,
+  /// Request body template. When unset, the inbound message value is sent
+  /// instead (JSON values are serialized; empty messages send no body).
+  #[serde(default)]
+  pub body: Option<String>,
+  /// Per-attempt deadline. No deadline when unset.
+  #[serde(default)]
+  pub timeout_ms: Option<u64>,
+  /// Extra attempts after a failed request or timeout. Policy rejections
+  /// (`HostNotAllowed`, `InvalidUrl`) are never retried.
+  #[serde(default)]
+  pub retries: u32,
+  #[serde(default)]
+  pub retry_backoff_ms: u64,
+  /// Response statuses that count as a failed attempt and are retried.
+  /// When unset, any 5xx is retried. Once retries are spent, the last
+  /// response is handled as usual.
+  #[serde(default)]
+  pub retry_statuses: Option<Vec<u16>>,
+  /// End the actor with an error when the final response is not 2xx,
+  /// instead of emitting it.
+  #[serde(default)]
+  pub error_for_status: bool,
+  /// JSON pointer (e.g. `/data/items`) into the parsed response body. When
+  /// set, only that value is emitted instead of the full response object.
+  #[serde(default)]
+  pub extract: Option<String>,
+  /// Fail when a template touches an undefined variable or attribute,
+  /// instead of rendering it empty.
+  #[serde(default)]
+  pub strict: bool,
+}
+
+fn default_method() -> String {
+  "GET".into()
+}
+
+/// The request templates, compiled once when the actor starts.
+struct RequestTemplates<'a> {
+  url: Template<'a, 'a>,
+  headers: Vec<(&'a str, Template<'a, 'a>)>,
+  body: Option<Template<'a, 'a>>,
+}
+
+impl<'a> RequestTemplates<'a> {
+  fn compile(env: &'a Environment<'a>, config: &'a HttpConfig) -> Result<Self, ActorError> {
+    let compile = |name: &'a str, source: &'a str| {
+      env
+        .template_from_named_str(name, source)
+        .map_err(|e| ActorError::Other(format!("http {name} template: {}", describe(&e))))
+    };
+    Ok(Self {
+      url: compile("url", &config.url)?,
+      headers: config
+        .headers
+        .iter()
+        .map(|(name, value)| Ok((name.as_str(), compile("headers", value)?)))
+        .collect::<Result<_, ActorError>>()?,
+      body: config
+        .body
+        .as_deref()
+        .map(|body| compile("body", body))
+        .transpose()?,
+    })
+  }
+}
+
+/// Native actor that performs one HTTP request per inbound message.
+///
+/// Emits an `"http.response"` message whose JSON value is
+/// `{ status, headers, body }` — `body` is parsed as JSON when possible,
+/// otherwise kept as a string. The inbound `correlation_id` is carried
+/// over. `url`, header values, and `body` are templates rendered against
+/// the inbound message. Transport errors, timeouts, and retryable statuses
+/// (5xx unless `retry_statuses` says otherwise) use up `retries`. A request
+/// that still fails after them ends the actor with an error, same as a
+/// failing Wasm or Lua `handle`. A final non-2xx response is emitted unless
+/// `error_for_status` is set.
+pub struct HttpActor {
+  http: Arc<dyn HttpClient>,
+  config: HttpConfig,
+}
+
+impl HttpActor {
+  pub fn new(http: Arc<dyn HttpClient>, config: HttpConfig) -> Self {
+    Self { http, config }
+  }
+
+  fn request_for(
+    &self,
+    templates: &RequestTemplates<'_>,
+    msg: &Message,
+  ) -> Result<HttpRequest, ActorError> {
+    let mut headers = templates
+      .headers
+      .iter()
+      .map(|(name, value)| Ok(((*name).to_owned(), render(value, msg)?)))
+      .collect::<Result<HashMap<_, _>, ActorError>>()?;
+    let body = match (&templates.body, &msg.value) {
+      (Some(body), _) => Some(render(body, msg)?),
+      (None, MessageValue::Json(v)) => {
+        if !headers
+          .keys()
+          .any(|k| k.eq_ignore_ascii_case("content-type"))
+        {
+          headers.insert("content-type".into(), "application/json".into());
+        }
+        Some(serde_json::to_string(v)?)
+      }
+      (None, MessageValue::Binary(b)) => Some(
+        std::str::from_utf8(b)
+          .map_err(|e| ActorError::Other(format!("http body is not utf-8: {e}")))?
+          .to_owned(),
+      ),
+      (None, MessageValue::Empty) => None,
+    };
+    Ok(HttpRequest {
+      // `HttpRequest` owns its method, so it is copied per message.
+      method: self.config.method.clone(),
+      url: render(&templates.url, msg)?,
+      headers,
+      body,
+    })
+  }
+
+  fn retryable_status(&self, status: u16) -> bool {
+    match &self.config.retry_statuses {
+      Some(statuses) => statuses.contains(&status),
+      None => (500..600).contains(&status),
+    }
+  }
+
+  async fn send_once(&self, req: HttpRequest) -> Result<HttpResponse, HttpError> {
+    match self.config.timeout_ms {
+      Some(ms) => tokio::time::timeout(Duration::from_millis(ms), self.http.send(req))
+        .await
+        .map_err(|_| HttpError::RequestFailed(format!("timed out after {ms}ms")))?,
+      None => self.http.send(req).await,
+    }
+  }
+
+  /// Waits out the retry backoff. Returns `false` if the workflow was
+  /// cancelled first.
+  async fn backoff(&self, ctx: &Context) -> bool {
+    tokio::select! {
+      _ = ctx.cancelled() => false,
+      _ = tokio::time::sleep(Duration::from_millis(self.config.retry_backoff_ms)) => true,
+    }
+  }
+
+  /// `None` when the workflow is cancelled during a backoff.
+  async fn send_with_retries(
+    &self,
+    req: HttpRequest,
+    ctx: &Context,
+  ) -> Result<Option<HttpResponse>, ActorError> {
+    for attempt in 1..=self.config.retries {
+      // The client takes the request by value; only retryable attempts
+      // pay for a copy.
+      match self.send_once(req.clone()).await {
+        Ok(resp) if self.retryable_status(resp.status) => {
+          tracing::debug!(
+            status = resp.status,
+            attempt,
+            "http status retryable, retrying"
+          );
+          if !self.backoff(ctx).await {
+            return Ok(None);
+          }
+        }
+        Ok(resp) => return Ok(Some(resp)),
+        Err(e @ (HttpError::HostNotAllowed { .. } | HttpError::InvalidUrl(_))) => {
+          return Err(ActorError::Other(format!("http request failed: {e}")));
+        }
+        Err(e) => {
+          tracing::debug!(error = %e, attempt, "http request failed, retrying");
+          if !self.backoff(ctx).await {
+            return Ok(None);
+          }
+        }
+      }
+    }
+    self
+      .send_once(req)
+      .await
+      .map(Some)
+      .map_err(|e| ActorError::Other(format!("http request failed: {e}")))
+  }
+
+  fn response_value(&self, resp: HttpResponse) -> Result<Value, ActorError> {
+    if self.config.error_for_status && !(200..300).contains(&resp.status) {
+      return Err(ActorError::Other(format!(
+        "http request failed: status {}",
+        resp.status
+      )));
+    }
+    let body = serde_json::from_str::<Value>(&resp.body).unwrap_or(Value::String(resp.body));
+    match &self.config.extract {
+      Some(pointer) => body
+        .pointer(pointer)
+        .cloned()
+        .ok_or_else(|| ActorError::Other(format!("http response has no value at '{pointer}'"))),
+      None => Ok(json!({
+        "status": resp.status,
+        "headers": resp.headers,
+        "body": body,
+      })),
+    }
+  }
+}
+
+#[async_trait]
+impl Actor for HttpActor {
+  async fn run(&self, mut inbox: Inbox, emit: Emitter, ctx: Context) -> Result<(), ActorError> {
+    let env = environment(self.config.strict);
+    let templates = RequestTemplates::compile(&env, &self.config)?;
+
+    loop {
+      let msg = tokio::select! {
+        _ = ctx.cancelled() => return Ok(()),
+        msg = inbox.recv() => msg,
+      };
+      let Some(msg) = msg else {
+        return Ok(());
+      };
+
+      let req = self.request_for(&templates, &msg)?;
+      let Some(resp) = self.send_with_retries(req, &ctx).await? else {
+        return Ok(());
+      };
+      let value = self.response_value(resp)?;
+
+      emit
+        .send(Message {
+          type_: "http.response".into(),
+          correlation_id: msg.correlation_id,
+          value: MessageValue::Json(value),
+        })
+        .await?;
+    }
+  }
+}
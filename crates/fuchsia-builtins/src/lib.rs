@@ -0,0 +1,29 @@
+//! Built-in native actors for common workflow steps.
+//!
+//! Each actor is a plain [`Actor`] impl plus a `serde`-deserializable
+//! config type. Nothing is registered automatically — hosts register the
+//! actors they want under whatever keys they like (conventionally
+//! `fuchsia.*`), injecting capabilities through the factory closure:
+//!
+//! ```ignore
+//! registry.register::<HttpActor, HttpConfig, _>("fuchsia.http", move |cfg| {
+//!   HttpActor::new(Arc::clone(&http), cfg)
+//! });
+//! ```
+//!
+//! Templates in built-in configs run under the limits in [`template`].
+//!
+//! [`Actor`]: fuchsia_actor::Actor
+
+pub mod debounce;
+pub mod dedup;
+pub mod filter;
+pub mod http;
+pub mod template;
+pub mod transform;
+
+pub use debounce::{DebounceActor, DebounceConfig};
+pub use dedup::{DedupActor, DedupConfig};
+pub use filter::{FilterActor, FilterConfig};
+pub use http::{HttpActor, HttpConfig};
+pub use transform::{TransformActor, TransformConfig};
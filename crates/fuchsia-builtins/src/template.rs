@@ -0,0 +1,114 @@
+//! Shared minijinja setup for the template-driven built-ins.
+//!
+//! Templates come from workflow config, so every environment is built with
+//! hard limits: a fuel budget bounding instructions per render, a shallow
+//! recursion limit, and a cap on rendered output. Breaching any of them is
+//! a render error, which ends the actor like any other.
+
+use fuchsia_actor::{ActorError, Message, MessageValue};
+use minijinja::{Environment, Template, UndefinedBehavior};
+use std::io;
+
+/// Instructions a single render may execute before it is aborted.
+pub const FUEL: u64 = 100_000;
+
+/// Nesting depth for loops, conditionals, and macro calls.
+pub const RECURSION_LIMIT: usize = 64;
+
+/// Largest rendered output, in bytes.
+pub const MAX_OUTPUT_BYTES: usize = 1024 * 1024;
+
+/// A fresh environment with the sandbox limits applied. `strict` makes
+/// any use of an undefined variable or attribute a render error instead of
+/// rendering empty.
+pub(crate) fn environment(strict: bool) -> Environment<'static> {
+  let mut env = Environment::new();
+  // Debug info records the failing span even in release builds, so errors
+  // can quote the expression that broke (see `describe`).
+  env.set_debug(true);
+  env.set_fuel(Some(FUEL));
+  env.set_recursion_limit(RECURSION_LIMIT);
+  if strict {
+    env.set_undefined_behavior(UndefinedBehavior::Strict);
+  }
+  env
+}
+
+fn template_value(value: &MessageValue) -> Result<minijinja::Value, ActorError> {
+  Ok(match value {
+    MessageValue::Json(v) => minijinja::Value::from_serialize(v),
+    MessageValue::Binary(b) => minijinja::Value::from(
+      std::str::from_utf8(b)
+        .map_err(|e| ActorError::Other(format!("template input is not utf-8: {e}")))?,
+    ),
+    MessageValue::Empty => minijinja::Value::from(()),
+  })
+}
+
+/// `e`, plus the source span it failed on when minijinja recorded one. For
+/// a strict-mode failure that span is the undefined expression itself, so
+/// `undefined value (in key:1)` becomes `... : \`value.id\``.
+pub(crate) fn describe(e: &minijinja::Error) -> String {
+  let span = e
+    .template_source()
+    .zip(e.range())
+    .and_then(|(src, range)| src.get(range));
+  match span {
+    Some(expr) => format!("{e}: `{expr}`"),
+    None => e.to_string(),
+  }
+}
+
+/// Output sink that refuses writes past [`MAX_OUTPUT_BYTES`].
+struct CappedBuf {
+  buf: Vec<u8>,
+  overflowed: bool,
+}
+
+impl io::Write for CappedBuf {
+  fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+    if self.buf.len() + data.len() > MAX_OUTPUT_BYTES {
+      self.overflowed = true;
+      return Err(io::Error::other("output limit exceeded"));
+    }
+    self.buf.extend_from_slice(data);
+    Ok(data.len())
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    Ok(())
+  }
+}
+
+/// The shared message context (`value`, `type`, `correlation_id`) seen by
+/// every template and expression in the built-ins.
+pub(crate) fn context(msg: &Message) -> Result<minijinja::Value, ActorError> {
+  Ok(minijinja::context! {
+    value => template_value(&msg.value)?,
+    type => &msg.type_,
+    correlation_id => &msg.correlation_id,
+  })
+}
+
+/// Render `template` against the shared message [`context`]. Compile
+/// templates with `template_from_named_str`, using the config field name
+/// (`template`, `key`), so errors say which field failed.
+pub(crate) fn render(template: &Template<'_, '_>, msg: &Message) -> Result<String, ActorError> {
+  let ctx = context(msg)?;
+  let mut out = CappedBuf {
+    buf: Vec::new(),
+    overflowed: false,
+  };
+  if let Err(e) = template.render_captured_to(ctx, &mut out) {
+    if out.overflowed {
+      return Err(ActorError::Other(format!(
+        "template render: output exceeds {MAX_OUTPUT_BYTES} bytes"
+      )));
+    }
+    return Err(ActorError::Other(format!(
+      "template render: {}",
+      describe(&e)
+    )));
+  }
+  String::from_utf8(out.buf).map_err(|e| ActorError::Other(format!("template render: {e}")))
+}
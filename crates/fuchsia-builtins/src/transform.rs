@@ -0,0 +1,78 @@
+use crate::template::{describe, environment, render};
+use async_trait::async_trait;
+use fuchsia_actor::{Actor, ActorError, Context, Emitter, Inbox, Message, MessageValue};
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Per-node configuration for [`TransformActor`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct TransformConfig {
+  /// minijinja template rendered once per message.
+  pub template: String,
+  /// Type for emitted messages. Defaults to the inbound message's type.
+  #[serde(default)]
+  pub emit_type: Option<String>,
+  /// Fail the render when the template touches an undefined variable or
+  /// attribute, instead of rendering it empty.
+  #[serde(default)]
+  pub strict: bool,
+}
+
+/// Native actor that reshapes each inbound message with a minijinja
+/// template.
+///
+/// The template sees `value` (the message value — JSON as-is, binary as a
+/// UTF-8 string, empty as `none`), `type`, and `correlation_id`. Rendered
+/// output that parses as JSON is emitted as that JSON value; anything else
+/// is emitted as a JSON string. The template is compiled once when the
+/// actor starts; a compile or render error — including breaching the
+/// [sandbox limits](crate::template) — ends the actor.
+pub struct TransformActor {
+  config: TransformConfig,
+}
+
+impl TransformActor {
+  pub fn new(config: TransformConfig) -> Self {
+    Self { config }
+  }
+}
+
+#[async_trait]
+impl Actor for TransformActor {
+  async fn run(&self, mut inbox: Inbox, emit: Emitter, ctx: Context) -> Result<(), ActorError> {
+    let env = environment(self.config.strict);
+    let template = env
+      .template_from_named_str("template", &self.config.template)
+      .map_err(|e| ActorError::Other(format!("transform template: {}", describe(&e))))?;
+
+    loop {
+      let msg = tokio::select! {
+        _ = ctx.cancelled() => return Ok(()),
+        msg = inbox.recv() => msg,
+      };
+      let Some(msg) = msg else {
+        return Ok(());
+      };
+
+      let rendered = render(&template, &msg)?;
+
+      let value = match serde_json::from_str::<Value>(&rendered) {
+        Ok(v) => v,
+        Err(_) => Value::String(rendered),
+      };
+
+      emit
+        .send(Message {
+          type_: self
+            .config
+            .emit_type
+            .as_deref()
+            .unwrap_or(&msg.type_)
+            .to_owned(),
+          correlation_id: msg.correlation_id,
+          value: MessageValue::Json(value),
+        })
+        .await?;
+    }
+  }
+}
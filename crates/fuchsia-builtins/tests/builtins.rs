@@ -0,0 +1,740 @@
+//! Integration tests for the built-in actors, driven through
+//! `fuchsia-runtime` with a recording sink downstream.
+
+use async_trait::async_trait;
+use fuchsia_actor::{Actor, ActorError, Context, Emitter, Inbox, Message, MessageValue};
+use fuchsia_builtins::{
+  DebounceActor, DebounceConfig, DedupActor, DedupConfig, FilterActor, FilterConfig, HttpActor,
+  HttpConfig, TransformActor, TransformConfig,
+};
+use fuchsia_capabilities::http::{HttpClient, HttpError, HttpRequest, HttpResponse};
+use fuchsia_runtime::{ActorRegistry, Edge, EdgeKind, Graph, Node, Orchestrator};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+// ---- Test doubles -------------------------------------------------------
+
+struct Recorder {
+  out: Arc<Mutex<Vec<Message>>>,
+}
+
+#[async_trait]
+impl Actor for Recorder {
+  async fn run(&self, mut inbox: Inbox, _emit: Emitter, ctx: Context) -> Result<(), ActorError> {
+    loop {
+      tokio::select! {
+          _ = ctx.cancelled() => return Ok(()),
+          msg = inbox.recv() => match msg {
+              Some(msg) => self.out.lock().unwrap().push(msg),
+              None => return Ok(()),
+          }
+      }
+    }
+  }
+}
+
+/// Records every request and answers from a queue of canned results.
+#[derive(Default)]
+struct MockHttp {
+  requests: Mutex<Vec<HttpRequest>>,
+  responses: Mutex<Vec<Result<HttpResponse, HttpError>>>,
+}
+
+impl MockHttp {
+  fn with_responses(responses: Vec<Result<HttpResponse, HttpError>>) -> Arc<Self> {
+    Arc::new(Self {
+      requests: Mutex::new(Vec::new()),
+      responses: Mutex::new(responses),
+    })
+  }
+}
+
+#[async_trait]
+impl HttpClient for MockHttp {
+  async fn send(&self, req: HttpRequest) -> Result<HttpResponse, HttpError> {
+    self.requests.lock().unwrap().push(req);
+    self.responses.lock().unwrap().remove(0)
+  }
+}
+
+fn ok_response(body: &str) -> Result<HttpResponse, HttpError> {
+  Ok(HttpResponse {
+    status: 200,
+    headers: HashMap::new(),
+    body: body.into(),
+  })
+}
+
+// ---- Helpers ------------------------------------------------------------
+
+/// Runs `entry → rec`, sends `inputs`, joins, and returns what `rec` saw
+/// plus the per-actor results.
+async fn run_into_recorder(
+  registry: ActorRegistry,
+  actor: &str,
+  config: Value,
+  inputs: Vec<Message>,
+) -> (Vec<Message>, Vec<Result<(), ActorError>>) {
+  let out = Arc::new(Mutex::new(Vec::new()));
+  let mut registry = registry;
+  {
+    let out = out.clone();
+    registry.register::<Recorder, Value, _>("recorder", move |_| Recorder { out: out.clone() });
+  }
+
+  let graph = Graph {
+    entry: "under_test".into(),
+    nodes: vec![
+      Node {
+        id: "under_test".into(),
+        actor: actor.into(),
+        config,
+      },
+      Node {
+        id: "rec".into(),
+        actor: "recorder".into(),
+        config: Value::Null,
+      },
+    ],
+    edges: vec![Edge {
+      from: "under_test".into(),
+      to: "rec".into(),
+      kind: EdgeKind::Data,
+    }],
+  };
+
+  let handle = Orchestrator::new(Arc::new(registry))
+    .start(&graph)
+    .expect("start workflow");
+  for msg in inputs {
+    handle.send(msg).await.expect("send input");
+  }
+  let results = handle.join().await;
+  let recorded = out.lock().unwrap().clone();
+  (recorded, results)
+}
+
+fn http_registry(http: Arc<MockHttp>) -> ActorRegistry {
+  let mut registry = ActorRegistry::new();
+  registry.register::<HttpActor, HttpConfig, _>("fuchsia.http", move |cfg| {
+    HttpActor::new(http.clone(), cfg)
+  });
+  registry
+}
+
+// ---- http ---------------------------------------------------------------
+
+#[tokio::test]
+async fn http_posts_message_and_emits_response() {
+  let http = MockHttp::with_responses(vec![ok_response(r#"{"id": 7}"#)]);
+
+  let (recorded, results) = run_into_recorder(
+    http_registry(http.clone()),
+    "fuchsia.http",
+    json!({ "method": "POST", "url": "https://api.example.com/items" }),
+    vec![
+      Message::with_type("item")
+        .with_correlation_id("c-1")
+        .json(json!({ "name": "widget" })),
+    ],
+  )
+  .await;
+  assert!(results.iter().all(Result::is_ok), "{results:?}");
+
+  let requests = http.requests.lock().unwrap();
+  assert_eq!(requests.len(), 1);
+  assert_eq!(requests[0].method, "POST");
+  assert_eq!(requests[0].url, "https://api.example.com/items");
+  assert_eq!(requests[0].headers["content-type"], "application/json");
+  assert_eq!(
+    serde_json::from_str::<Value>(requests[0].body.as_deref().unwrap()).unwrap(),
+    json!({ "name": "widget" })
+  );
+
+  assert_eq!(recorded.len(), 1);
+  assert_eq!(recorded[0].type_, "http.response");
+  assert_eq!(recorded[0].correlation_id.as_deref(), Some("c-1"));
+  let MessageValue::Json(v) = &recorded[0].value else {
+    panic!("expected JSON response, got {:?}", recorded[0].value);
+  };
+  assert_eq!(v["status"], json!(200));
+  assert_eq!(v["body"], json!({ "id": 7 }));
+}
+
+#[tokio::test]
+async fn http_retries_then_extracts() {
+  let http = MockHttp::with_responses(vec![
+    Err(HttpError::RequestFailed("connection reset".into())),
+    ok_response(r#"{"data": {"items": [1, 2]}}"#),
+  ]);
+
+  let (recorded, results) = run_into_recorder(
+    http_registry(http.clone()),
+    "fuchsia.http",
+    json!({ "url": "https://api.example.com/items", "retries": 1, "extract": "/data/items" }),
+    vec![Message::with_type("tick").empty()],
+  )
+  .await;
+  assert!(results.iter().all(Result::is_ok), "{results:?}");
+
+  let requests = http.requests.lock().unwrap();
+  assert_eq!(requests.len(), 2);
+  assert_eq!(requests[0].method, "GET");
+  assert!(requests[0].body.is_none());
+
+  assert_eq!(recorded.len(), 1);
+  assert!(matches!(&recorded[0].value, MessageValue::Json(v) if *v == json!([1, 2])));
+}
+
+#[tokio::test]
+async fn http_does_not_retry_policy_rejection() {
+  let http = MockHttp::with_responses(vec![Err(HttpError::HostNotAllowed {
+    host: "evil.com".into(),
+  })]);
+
+  let (recorded, results) = run_into_recorder(
+    http_registry(http.clone()),
+    "fuchsia.http",
+    json!({ "url": "https://evil.com/", "retries": 3 }),
+    vec![Message::with_type("tick").empty()],
+  )
+  .await;
+
+  assert_eq!(http.requests.lock().unwrap().len(), 1);
+  assert!(recorded.is_empty());
+  assert!(matches!(&results[0], Err(ActorError::Other(m)) if m.contains("evil.com")));
+}
+
+fn status_response(status: u16) -> Result<HttpResponse, HttpError> {
+  Ok(HttpResponse {
+    status,
+    headers: HashMap::new(),
+    body: String::new(),
+  })
+}
+
+#[tokio::test]
+async fn http_retries_server_errors() {
+  let http = MockHttp::with_responses(vec![status_response(503), ok_response(r#"{"ok": true}"#)]);
+
+  let (recorded, results) = run_into_recorder(
+    http_registry(http.clone()),
+    "fuchsia.http",
+    json!({ "url": "https://api.example.com/items", "retries": 2 }),
+    vec![Message::with_type("tick").empty()],
+  )
+  .await;
+  assert!(results.iter().all(Result::is_ok), "{results:?}");
+  assert_eq!(http.requests.lock().unwrap().len(), 2);
+  assert!(matches!(&recorded[0].value, MessageValue::Json(v) if v["status"] == json!(200)));
+}
+
+#[tokio::test(start_paused = true)]
+async fn http_cancel_interrupts_retry_backoff() {
+  let http = MockHttp::with_responses(vec![status_response(503), status_response(503)]);
+  let registry = http_registry(http.clone());
+  let graph = Graph {
+    entry: "http".into(),
+    nodes: vec![Node {
+      id: "http".into(),
+      actor: "fuchsia.http".into(),
+      config: json!({
+        "url": "https://api.example.com/items",
+        "retries": 1,
+        "retry_backoff_ms": 60_000,
+      }),
+    }],
+    edges: vec![],
+  };
+
+  let handle = Orchestrator::new(Arc::new(registry)).start(&graph).unwrap();
+  let started = tokio::time::Instant::now();
+  handle
+    .send(Message::with_type("tick").empty())
+    .await
+    .unwrap();
+  // Let the first attempt fail and the actor enter its backoff.
+  tokio::time::sleep(Duration::from_millis(10)).await;
+  handle.cancel();
+  let results = handle.join().await;
+
+  assert!(results.iter().all(Result::is_ok), "{results:?}");
+  assert_eq!(http.requests.lock().unwrap().len(), 1);
+  assert!(
+    started.elapsed() < Duration::from_secs(1),
+    "waited out the backoff"
+  );
+}
+
+#[tokio::test]
+async fn http_error_for_status_fails_non_2xx() {
+  let http = MockHttp::with_responses(vec![status_response(404)]);
+
+  let (recorded, results) = run_into_recorder(
+    http_registry(http.clone()),
+    "fuchsia.http",
+    json!({ "url": "https://api.example.com/items", "retries": 2, "error_for_status": true }),
+    vec![Message::with_type("tick").empty()],
+  )
+  .await;
+
+  // 404 is not retryable by default, so there is exactly one attempt.
+  assert_eq!(http.requests.lock().unwrap().len(), 1);
+  assert!(recorded.is_empty());
+  assert!(
+    matches!(&results[0], Err(ActorError::Other(m)) if m.contains("status 404")),
+    "{results:?}"
+  );
+}
+
+#[tokio::test]
+async fn http_renders_templates_per_message() {
+  let http = MockHttp::with_responses(vec![ok_response("{}")]);
+
+  let (_, results) = run_into_recorder(
+    http_registry(http.clone()),
+    "fuchsia.http",
+    json!({
+      "method": "PUT",
+      "url": "https://api.example.com/items/{{ value.id }}",
+      "headers": { "x-request-id": "{{ correlation_id }}" },
+      "body": "{\"name\": {{ value.name | tojson }}}"
+    }),
+    vec![
+      Message::with_type("item")
+        .with_correlation_id("c-9")
+        .json(json!({ "id": 42, "name": "widget" })),
+    ],
+  )
+  .await;
+  assert!(results.iter().all(Result::is_ok), "{results:?}");
+
+  let requests = http.requests.lock().unwrap();
+  assert_eq!(requests[0].url, "https://api.example.com/items/42");
+  assert_eq!(requests[0].headers["x-request-id"], "c-9");
+  assert_eq!(requests[0].body.as_deref(), Some(r#"{"name": "widget"}"#));
+}
+
+// ---- transform ----------------------------------------------------------
+
+fn transform_registry() -> ActorRegistry {
+  let mut registry = ActorRegistry::new();
+  registry.register::<TransformActor, TransformConfig, _>("fuchsia.transform", TransformActor::new);
+  registry
+}
+
+#[tokio::test]
+async fn transform_reshapes_json() {
+  let template = r#"{"user": {{ value.name | tojson }}, "adults": {{ value.ages | select("ge", 18) | list | tojson }}}"#;
+
+  let (recorded, results) = run_into_recorder(
+    transform_registry(),
+    "fuchsia.transform",
+    json!({ "template": template, "emit_type": "reshaped" }),
+    vec![
+      Message::with_type("people")
+        .with_correlation_id("c-2")
+        .json(json!({ "name": "ada", "ages": [12, 30, 45] })),
+    ],
+  )
+  .await;
+  assert!(results.iter().all(Result::is_ok), "{results:?}");
+
+  assert_eq!(recorded.len(), 1);
+  assert_eq!(recorded[0].type_, "reshaped");
+  assert_eq!(recorded[0].correlation_id.as_deref(), Some("c-2"));
+  assert!(
+    matches!(&recorded[0].value, MessageValue::Json(v) if *v == json!({ "user": "ada", "adults": [30, 45] }))
+  );
+}
+
+#[tokio::test]
+async fn transform_emits_plain_text_as_string() {
+  let (recorded, results) = run_into_recorder(
+    transform_registry(),
+    "fuchsia.transform",
+    json!({ "template": "hello {{ value.name }}" }),
+    vec![Message::with_type("greet").json(json!({ "name": "ada" }))],
+  )
+  .await;
+  assert!(results.iter().all(Result::is_ok), "{results:?}");
+
+  assert_eq!(recorded[0].type_, "greet");
+  assert!(matches!(&recorded[0].value, MessageValue::Json(v) if *v == json!("hello ada")));
+}
+
+#[tokio::test]
+async fn transform_strict_rejects_undefined() {
+  let (recorded, results) = run_into_recorder(
+    transform_registry(),
+    "fuchsia.transform",
+    json!({ "template": "hello {{ value.nmae }}", "strict": true }),
+    vec![Message::with_type("greet").json(json!({ "name": "ada" }))],
+  )
+  .await;
+
+  assert!(recorded.is_empty());
+  assert!(
+    matches!(
+      &results[0],
+      Err(ActorError::Other(m))
+        if m.contains("undefined") && m.contains("(in template:1)") && m.contains("`value.nmae`")
+    ),
+    "{results:?}"
+  );
+}
+
+#[tokio::test]
+async fn transform_aborts_runaway_template() {
+  let (recorded, results) = run_into_recorder(
+    transform_registry(),
+    "fuchsia.transform",
+    json!({ "template": "{% for i in range(1000) %}{% for j in range(1000) %}{% endfor %}{% endfor %}" }),
+    vec![Message::with_type("spin").empty()],
+  )
+  .await;
+
+  assert!(recorded.is_empty());
+  assert!(
+    matches!(&results[0], Err(ActorError::Other(m)) if m.contains("fuel")),
+    "{results:?}"
+  );
+}
+
+#[tokio::test]
+async fn transform_caps_output_size() {
+  let (recorded, results) = run_into_recorder(
+    transform_registry(),
+    "fuchsia.transform",
+    json!({ "template": "{% for i in range(2000) %}{{ value }}{% endfor %}" }),
+    vec![Message::with_type("big").json(json!("x".repeat(1024)))],
+  )
+  .await;
+
+  assert!(recorded.is_empty());
+  assert!(
+    matches!(&results[0], Err(ActorError::Other(m)) if m.contains("output exceeds")),
+    "{results:?}"
+  );
+}
+
+// ---- debounce -----------------------------------------------------------
+
+#[tokio::test(start_paused = true)]
+async fn debounce_collapses_bursts_per_key() {
+  let out = Arc::new(Mutex::new(Vec::new()));
+  let mut registry = ActorRegistry::new();
+  registry.register::<DebounceActor, DebounceConfig, _>("fuchsia.debounce", DebounceActor::new);
+  {
+    let out = out.clone();
+    registry.register::<Recorder, Value, _>("recorder", move |_| Recorder { out: out.clone() });
+  }
+
+  let graph = Graph {
+    entry: "deb".into(),
+    nodes: vec![
+      Node {
+        id: "deb".into(),
+        actor: "fuchsia.debounce".into(),
+        config: json!({ "window_ms": 50, "key": "{{ value.device }}" }),
+      },
+      Node {
+        id: "rec".into(),
+        actor: "recorder".into(),
+        config: Value::Null,
+      },
+    ],
+    edges: vec![Edge {
+      from: "deb".into(),
+      to: "rec".into(),
+      kind: EdgeKind::Data,
+    }],
+  };
+
+  let handle = Orchestrator::new(Arc::new(registry)).start(&graph).unwrap();
+  // Time is paused: each 1 ms sleep lets the actor take the message, then
+  // advances the clock, so per-key deadlines are strictly ordered (b < a).
+  for (device, n) in [("a", 1), ("b", 1), ("a", 2), ("a", 3)] {
+    handle
+      .send(Message::with_type("reading").json(json!({ "device": device, "n": n })))
+      .await
+      .unwrap();
+    tokio::time::sleep(Duration::from_millis(1)).await;
+  }
+  tokio::time::sleep(Duration::from_millis(120)).await;
+  assert_eq!(
+    out.lock().unwrap().len(),
+    2,
+    "bursts should flush after the window"
+  );
+
+  handle
+    .send(Message::with_type("reading").json(json!({ "device": "b", "n": 2 })))
+    .await
+    .unwrap();
+  let results = handle.join().await;
+  assert!(results.iter().all(Result::is_ok), "{results:?}");
+
+  let recorded: Vec<Value> = out
+    .lock()
+    .unwrap()
+    .iter()
+    .map(|m| match &m.value {
+      MessageValue::Json(v) => v.clone(),
+      other => panic!("expected JSON, got {other:?}"),
+    })
+    .collect();
+  assert_eq!(
+    recorded,
+    vec![
+      json!({ "device": "b", "n": 1 }),
+      json!({ "device": "a", "n": 3 }),
+      // Still pending when the inbox closed; flushed on shutdown.
+      json!({ "device": "b", "n": 2 }),
+    ]
+  );
+}
+
+#[tokio::test(start_paused = true)]
+async fn debounce_max_keys_emits_oldest_early() {
+  let out = Arc::new(Mutex::new(Vec::new()));
+  let mut registry = ActorRegistry::new();
+  registry.register::<DebounceActor, DebounceConfig, _>("fuchsia.debounce", DebounceActor::new);
+  {
+    let out = out.clone();
+    registry.register::<Recorder, Value, _>("recorder", move |_| Recorder { out: out.clone() });
+  }
+
+  let graph = Graph {
+    entry: "deb".into(),
+    nodes: vec![
+      Node {
+        id: "deb".into(),
+        actor: "fuchsia.debounce".into(),
+        config: json!({ "window_ms": 60_000, "key": "{{ value.id }}", "max_keys": 2 }),
+      },
+      Node {
+        id: "rec".into(),
+        actor: "recorder".into(),
+        config: Value::Null,
+      },
+    ],
+    edges: vec![Edge {
+      from: "deb".into(),
+      to: "rec".into(),
+      kind: EdgeKind::Data,
+    }],
+  };
+
+  let handle = Orchestrator::new(Arc::new(registry)).start(&graph).unwrap();
+  let ids = |out: &Mutex<Vec<Message>>| -> Vec<Value> {
+    out
+      .lock()
+      .unwrap()
+      .iter()
+      .map(|m| match &m.value {
+        MessageValue::Json(v) => v["id"].clone(),
+        other => panic!("expected JSON, got {other:?}"),
+      })
+      .collect()
+  };
+  // The repeat of "a" pushes its deadline past "b"'s, so the third key
+  // evicts "b".
+  for id in ["a", "b", "a", "c"] {
+    handle
+      .send(Message::with_type("req").json(json!({ "id": id })))
+      .await
+      .unwrap();
+    tokio::time::sleep(Duration::from_millis(1)).await;
+  }
+  assert_eq!(ids(&out), vec![json!("b")]);
+
+  let results = handle.join().await;
+  assert!(results.iter().all(Result::is_ok), "{results:?}");
+  assert_eq!(ids(&out), vec![json!("b"), json!("a"), json!("c")]);
+}
+
+// ---- dedup --------------------------------------------------------------
+
+#[tokio::test]
+async fn dedup_suppresses_repeats_within_window() {
+  let mut registry = ActorRegistry::new();
+  registry.register::<DedupActor, DedupConfig, _>("fuchsia.dedup", DedupActor::new);
+
+  let (recorded, results) = run_into_recorder(
+    registry,
+    "fuchsia.dedup",
+    json!({ "window_ms": 60_000, "key": "{{ value.id }}" }),
+    vec![
+      Message::with_type("event").json(json!({ "id": 1, "attempt": 1 })),
+      Message::with_type("event").json(json!({ "id": 2, "attempt": 1 })),
+      Message::with_type("event").json(json!({ "id": 1, "attempt": 2 })),
+    ],
+  )
+  .await;
+  assert!(results.iter().all(Result::is_ok), "{results:?}");
+
+  let attempts: Vec<&Value> = recorded
+    .iter()
+    .map(|m| match &m.value {
+      MessageValue::Json(v) => v,
+      other => panic!("expected JSON, got {other:?}"),
+    })
+    .collect();
+  assert_eq!(
+    attempts,
+    vec![
+      &json!({ "id": 1, "attempt": 1 }),
+      &json!({ "id": 2, "attempt": 1 })
+    ]
+  );
+}
+
+#[tokio::test]
+async fn dedup_ignores_json_key_order() {
+  let mut registry = ActorRegistry::new();
+  registry.register::<DedupActor, DedupConfig, _>("fuchsia.dedup", DedupActor::new);
+  // Parsed from text so the key order survives when serde_json preserves it.
+  let event = |text: &str| Message::with_type("event").json(serde_json::from_str(text).unwrap());
+
+  let (recorded, results) = run_into_recorder(
+    registry,
+    "fuchsia.dedup",
+    json!({ "window_ms": 60_000 }),
+    vec![
+      event(r#"{ "a": 1, "b": { "x": 1, "y": 2 } }"#),
+      event(r#"{ "b": { "y": 2, "x": 1 }, "a": 1 }"#),
+      event(r#"{ "a": 1, "b": { "x": 1, "y": 3 } }"#),
+    ],
+  )
+  .await;
+  assert!(results.iter().all(Result::is_ok), "{results:?}");
+  assert_eq!(recorded.len(), 2, "{recorded:?}");
+}
+
+#[tokio::test(start_paused = true)]
+async fn dedup_window_expires() {
+  let mut registry = ActorRegistry::new();
+  registry.register::<DedupActor, DedupConfig, _>("fuchsia.dedup", DedupActor::new);
+  let out = Arc::new(Mutex::new(Vec::new()));
+  {
+    let out = out.clone();
+    registry.register::<Recorder, Value, _>("recorder", move |_| Recorder { out: out.clone() });
+  }
+
+  let graph = Graph {
+    entry: "dedup".into(),
+    nodes: vec![
+      Node {
+        id: "dedup".into(),
+        actor: "fuchsia.dedup".into(),
+        config: json!({ "window_ms": 30 }),
+      },
+      Node {
+        id: "rec".into(),
+        actor: "recorder".into(),
+        config: Value::Null,
+      },
+    ],
+    edges: vec![Edge {
+      from: "dedup".into(),
+      to: "rec".into(),
+      kind: EdgeKind::Data,
+    }],
+  };
+
+  let handle = Orchestrator::new(Arc::new(registry)).start(&graph).unwrap();
+  let same = || Message::with_type("event").json(json!({ "id": 1 }));
+  handle.send(same()).await.unwrap();
+  handle.send(same()).await.unwrap();
+  tokio::time::sleep(Duration::from_millis(80)).await;
+  handle.send(same()).await.unwrap();
+  let results = handle.join().await;
+  assert!(results.iter().all(Result::is_ok), "{results:?}");
+
+  assert_eq!(out.lock().unwrap().len(), 2);
+}
+
+// ---- filter -------------------------------------------------------------
+
+fn filter_registry() -> ActorRegistry {
+  let mut registry = ActorRegistry::new();
+  registry.register::<FilterActor, FilterConfig, _>("fuchsia.filter", FilterActor::new);
+  registry
+}
+
+#[tokio::test]
+async fn filter_forwards_only_truthy() {
+  let (recorded, results) = run_into_recorder(
+    filter_registry(),
+    "fuchsia.filter",
+    json!({ "when": "value.status >= 500" }),
+    vec![
+      Message::with_type("resp").json(json!({ "status": 200 })),
+      Message::with_type("resp").json(json!({ "status": 503 })),
+      Message::with_type("resp").json(json!({ "status": 404 })),
+      Message::with_type("resp").json(json!({ "status": 500 })),
+    ],
+  )
+  .await;
+  assert!(results.iter().all(Result::is_ok), "{results:?}");
+
+  let statuses: Vec<_> = recorded
+    .iter()
+    .map(|m| match &m.value {
+      MessageValue::Json(v) => v["status"].clone(),
+      other => panic!("unexpected value {other:?}"),
+    })
+    .collect();
+  assert_eq!(statuses, vec![json!(503), json!(500)]);
+}
+
+#[tokio::test]
+async fn filter_strict_rejects_undefined() {
+  let msg = || Message::with_type("resp").json(json!({ "status": 200 }));
+
+  // Lenient: a missing field is falsy and the message is dropped.
+  let (recorded, results) = run_into_recorder(
+    filter_registry(),
+    "fuchsia.filter",
+    json!({ "when": "value.flag" }),
+    vec![msg()],
+  )
+  .await;
+  assert!(recorded.is_empty());
+  assert!(results.iter().all(Result::is_ok), "{results:?}");
+
+  for when in ["value.flag", "value.flag == 1"] {
+    let (recorded, results) = run_into_recorder(
+      filter_registry(),
+      "fuchsia.filter",
+      json!({ "when": when, "strict": true }),
+      vec![msg()],
+    )
+    .await;
+    assert!(recorded.is_empty());
+    assert!(
+      matches!(&results[0], Err(ActorError::Other(m)) if m.contains("undefined")),
+      "{when}: {results:?}"
+    );
+  }
+}
+
+#[tokio::test]
+async fn filter_rejects_bad_expression() {
+  let (recorded, results) = run_into_recorder(
+    filter_registry(),
+    "fuchsia.filter",
+    json!({ "when": "value.status >=" }),
+    vec![Message::with_type("resp").json(json!({ "status": 200 }))],
+  )
+  .await;
+
+  assert!(recorded.is_empty());
+  assert!(
+    matches!(&results[0], Err(ActorError::Other(m)) if m.contains("filter expression")),
+    "{results:?}"
+  );
+}
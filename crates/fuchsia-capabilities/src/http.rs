@@ -1,5 +1,6 @@
 use async_trait::async_trait;
 use std::collections::HashMap;
+use std::net::IpAddr;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -32,41 +33,105 @@ pub trait HttpClient: Send + Sync {
   async fn send(&self, req: HttpRequest) -> Result<HttpResponse, HttpError>;
 }
 
-/// Exact + wildcard-prefix allowed hosts policy.
+/// Exact + wildcard-prefix + CIDR allowed hosts policy.
 ///
 /// Patterns:
 /// - `*` — matches every host
 /// - `*.example.com` — matches `example.com` and any subdomain
 /// - `api.example.com` — exact match
+/// - `10.0.0.0/8`, `fd00::/8` — matches IP-literal hosts inside the range.
+///   Hostnames are not resolved, so a CIDR never admits a DNS name.
+///
+/// A pattern that looks like a CIDR but doesn't parse is kept as an exact
+/// match, which in practice matches nothing.
 #[derive(Debug, Clone, Default)]
 pub struct AllowedHosts {
-  patterns: Vec<String>,
+  patterns: Vec<HostPattern>,
+}
+
+#[derive(Debug, Clone)]
+enum HostPattern {
+  Any,
+  Subdomain(String),
+  Exact(String),
+  Cidr { net: IpAddr, prefix: u8 },
+}
+
+impl HostPattern {
+  fn parse(pattern: String) -> Self {
+    if pattern == "*" {
+      return Self::Any;
+    }
+    if let Some(suffix) = pattern.strip_prefix("*.") {
+      return Self::Subdomain(suffix.to_owned());
+    }
+    if let Some((addr, prefix)) = pattern.split_once('/')
+      && let (Ok(net), Ok(prefix)) = (addr.parse::<IpAddr>(), prefix.parse::<u8>())
+      && prefix <= address_bits(net)
+    {
+      return Self::Cidr { net, prefix };
+    }
+    Self::Exact(pattern)
+  }
+
+  fn matches(&self, host: &str) -> bool {
+    match self {
+      Self::Any => true,
+      Self::Subdomain(suffix) => {
+        host == suffix
+          || host
+            .strip_suffix(suffix.as_str())
+            .is_some_and(|rest| rest.ends_with('.'))
+      }
+      Self::Exact(pattern) => host == pattern,
+      Self::Cidr { net, prefix } => host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse::<IpAddr>()
+        .is_ok_and(|ip| in_cidr(ip, *net, *prefix)),
+    }
+  }
+}
+
+fn address_bits(addr: IpAddr) -> u8 {
+  match addr {
+    IpAddr::V4(_) => 32,
+    IpAddr::V6(_) => 128,
+  }
+}
+
+fn in_cidr(ip: IpAddr, net: IpAddr, prefix: u8) -> bool {
+  let (ip, net, bits) = match (ip, net) {
+    (IpAddr::V4(ip), IpAddr::V4(net)) => {
+      (u128::from(u32::from(ip)), u128::from(u32::from(net)), 32)
+    }
+    (IpAddr::V6(ip), IpAddr::V6(net)) => (u128::from(ip), u128::from(net), 128),
+    _ => return false,
+  };
+  // Shift out the host bits; a /0 shifts everything out and matches all.
+  let shift = u32::from(bits - prefix);
+  ip.checked_shr(shift).unwrap_or(0) == net.checked_shr(shift).unwrap_or(0)
 }
 
 impl AllowedHosts {
   pub fn new(patterns: impl IntoIterator<Item = impl Into<String>>) -> Self {
     Self {
-      patterns: patterns.into_iter().map(Into::into).collect(),
+      patterns: patterns
+        .into_iter()
+        .map(|p| HostPattern::parse(p.into()))
+        .collect(),
     }
   }
 
   /// Allow every host. Useful for tests; not recommended in production.
   pub fn all() -> Self {
     Self {
-      patterns: vec!["*".into()],
+      patterns: vec![HostPattern::Any],
     }
   }
 
   pub fn is_allowed(&self, host: &str) -> bool {
-    self.patterns.iter().any(|pattern| {
-      if pattern == "*" {
-        true
-      } else if let Some(suffix) = pattern.strip_prefix("*.") {
-        host == suffix || host.ends_with(&format!(".{suffix}"))
-      } else {
-        host == pattern
-      }
-    })
+    self.patterns.iter().any(|pattern| pattern.matches(host))
   }
 }
 
@@ -102,6 +167,7 @@ impl HttpClient for ReqwestHttp {
       .ok_or_else(|| HttpError::InvalidUrl("missing host".into()))?;
 
     if !self.allowed.is_allowed(host) {
+      tracing::warn!(host, "http request blocked by allowed_hosts");
       return Err(HttpError::HostNotAllowed {
         host: host.to_string(),
       });
@@ -174,4 +240,27 @@ mod tests {
     let allowed = AllowedHosts::default();
     assert!(!allowed.is_allowed("anything.com"));
   }
+
+  #[test]
+  fn cidr_v4() {
+    let allowed = AllowedHosts::new(["10.0.0.0/8"]);
+    assert!(allowed.is_allowed("10.1.2.3"));
+    assert!(!allowed.is_allowed("11.0.0.1"));
+    assert!(!allowed.is_allowed("ten.example.com"));
+  }
+
+  #[test]
+  fn cidr_v6_bracketed_host() {
+    let allowed = AllowedHosts::new(["fd00::/8"]);
+    assert!(allowed.is_allowed("[fd12::1]"));
+    assert!(!allowed.is_allowed("[fe80::1]"));
+    assert!(!allowed.is_allowed("10.0.0.1"));
+  }
+
+  #[test]
+  fn cidr_zero_prefix_and_malformed() {
+    assert!(AllowedHosts::new(["0.0.0.0/0"]).is_allowed("203.0.113.9"));
+    let malformed = AllowedHosts::new(["10.0.0.0/33"]);
+    assert!(!malformed.is_allowed("10.0.0.1"));
+  }
 }
@@ -2,7 +2,7 @@
 
 use async_trait::async_trait;
 use fuchsia_actor::{Actor, ActorError, Context, Emitter, Inbox, Message};
-use fuchsia_runtime::{ActorRegistry, Edge, Graph, Node};
+use fuchsia_runtime::{ActorRegistry, Edge, EdgeKind, Graph, Node};
 use serde_json::Value;
 
 pub struct Passthrough;
@@ -70,11 +70,13 @@ pub fn chain(k: usize) -> Graph {
     edges.push(Edge {
       from: format!("n{i}"),
       to: format!("n{}", i + 1),
+      kind: EdgeKind::Data,
     });
   }
   edges.push(Edge {
     from: format!("n{}", k - 1),
     to: "sink".into(),
+    kind: EdgeKind::Data,
   });
 
   Graph {
@@ -106,6 +108,58 @@ pub fn fan_out(width: usize) -> Graph {
     edges.push(Edge {
       from: "in".into(),
       to: id,
+      kind: EdgeKind::Data,
+    });
+  }
+
+  Graph {
+    entry: "in".into(),
+    nodes,
+    edges,
+  }
+}
+
+/// Lattice: entry passthrough → `width` parallel branches, each a chain of
+/// `depth` passthroughs ending in its own sink.
+/// Total node count = 1 + width * (depth + 1).
+pub fn lattice(width: usize, depth: usize) -> Graph {
+  assert!(width >= 1, "lattice requires width >= 1");
+  assert!(depth >= 1, "lattice requires depth >= 1");
+  let mut nodes = Vec::with_capacity(1 + width * (depth + 1));
+  let mut edges = Vec::with_capacity(width * (depth + 1));
+
+  nodes.push(Node {
+    id: "in".into(),
+    actor: "passthrough".into(),
+    config: Value::Null,
+  });
+
+  for w in 0..width {
+    let mut prev = "in".to_string();
+    for d in 0..depth {
+      let id = format!("b{w}n{d}");
+      nodes.push(Node {
+        id: id.clone(),
+        actor: "passthrough".into(),
+        config: Value::Null,
+      });
+      edges.push(Edge {
+        from: prev,
+        to: id.clone(),
+        kind: EdgeKind::Data,
+      });
+      prev = id;
+    }
+    let sink = format!("sink{w}");
+    nodes.push(Node {
+      id: sink.clone(),
+      actor: "sink".into(),
+      config: Value::Null,
+    });
+    edges.push(Edge {
+      from: prev,
+      to: sink,
+      kind: EdgeKind::Data,
     });
   }
 
@@ -119,3 +173,8 @@ pub fn fan_out(width: usize) -> Graph {
 pub fn bench_msg(i: u64) -> Message {
   Message::with_type("bench").json(serde_json::json!(i))
 }
+
+/// Binary message of `size` bytes, for measuring payload-size sensitivity.
+pub fn sized_msg(size: usize) -> Message {
+  Message::with_type("bench").binary(vec![0u8; size])
+}
@@ -0,0 +1,67 @@
+//! Synthetic DAG shapes: W parallel branches of depth D behind one entry.
+//!
+//! `lattice` sweeps width × depth with a small JSON payload, so scheduler
+//! cost can be read against both fan-out and chain length at once.
+//! `payload` holds the shape fixed and sweeps the binary message size, which
+//! surfaces per-edge clone cost on fan-out. Throughput is per input message.
+
+use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
+use fuchsia_actor::Message;
+use fuchsia_runtime::{ActorRegistry, Graph, Orchestrator};
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+mod common;
+use common::{bench_msg, lattice, registry, sized_msg};
+
+const MESSAGES_PER_ITER: u64 = 1_000;
+
+async fn run_once(reg: Arc<ActorRegistry>, graph: Graph, msg: impl Fn(u64) -> Message) {
+  let orch = Orchestrator::new(reg);
+  let handle = orch.start(&graph).expect("start workflow");
+  for i in 0..MESSAGES_PER_ITER {
+    handle.send(msg(i)).await.expect("send into entry");
+  }
+  let results = handle.join().await;
+  black_box(results);
+}
+
+fn bench_lattice(c: &mut Criterion) {
+  let rt = Runtime::new().expect("build tokio runtime");
+  let reg: Arc<ActorRegistry> = Arc::new(registry());
+
+  let mut group = c.benchmark_group("lattice");
+  group.throughput(Throughput::Elements(MESSAGES_PER_ITER));
+
+  for &(w, d) in &[(2usize, 2usize), (2, 8), (8, 2), (8, 8)] {
+    let graph = lattice(w, d);
+    let id = BenchmarkId::new("width_x_depth", format!("{w}x{d}"));
+    group.bench_with_input(id, &(w, d), |b, _| {
+      b.to_async(&rt)
+        .iter(|| run_once(reg.clone(), graph.clone(), bench_msg));
+    });
+  }
+
+  group.finish();
+}
+
+fn bench_payload(c: &mut Criterion) {
+  let rt = Runtime::new().expect("build tokio runtime");
+  let reg: Arc<ActorRegistry> = Arc::new(registry());
+  let graph = lattice(8, 1);
+
+  let mut group = c.benchmark_group("payload");
+  group.throughput(Throughput::Elements(MESSAGES_PER_ITER));
+
+  for &size in &[64usize, 4 * 1024, 64 * 1024] {
+    group.bench_with_input(BenchmarkId::new("bytes", size), &size, |b, &size| {
+      b.to_async(&rt)
+        .iter(|| run_once(reg.clone(), graph.clone(), move |_| sized_msg(size)));
+    });
+  }
+
+  group.finish();
+}
+
+criterion_group!(benches, bench_lattice, bench_payload);
+criterion_main!(benches);
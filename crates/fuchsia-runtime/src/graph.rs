@@ -1,22 +1,46 @@
+use fuchsia_actor::write_canonical;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Node {
+  /// Unique id within the graph; edges refer to nodes by it.
   pub id: String,
+  /// Registry key of the actor to instantiate.
   pub actor: String,
+  /// Deserialized into the actor's config type at instantiate time.
   #[serde(default)]
   pub config: Value,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// What an edge carries. `Data` edges receive everything the upstream
+/// emits; `OnError` edges receive a single `"error"` message when the
+/// upstream actor's `run` returns `Err`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeKind {
+  #[default]
+  Data,
+  OnError,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Edge {
   pub from: String,
   pub to: String,
+  #[serde(default)]
+  pub kind: EdgeKind,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Graph {
+  /// Id of the node that receives messages sent through the handle.
   pub entry: String,
   pub nodes: Vec<Node>,
   pub edges: Vec<Edge>,
@@ -26,4 +50,314 @@ impl Graph {
   pub fn edges_from<'a>(&'a self, node_id: &'a str) -> impl Iterator<Item = &'a Edge> + 'a {
     self.edges.iter().filter(move |e| e.from == node_id)
   }
+
+  /// Outgoing edges of `node_id` with the given kind.
+  pub fn edges_from_kind<'a>(
+    &'a self,
+    node_id: &'a str,
+    kind: EdgeKind,
+  ) -> impl Iterator<Item = &'a Edge> + 'a {
+    self.edges_from(node_id).filter(move |e| e.kind == kind)
+  }
+
+  /// JSON Schema (2020-12) describing the graph file format, for editor
+  /// autocompletion and CI validation of workflow files.
+  #[cfg(feature = "schema")]
+  pub fn json_schema() -> Value {
+    schemars::schema_for!(Graph).to_value()
+  }
+
+  /// Stable SHA-256 (hex) over the graph's meaning: entry, nodes, edges,
+  /// and node configs. Node and edge order and JSON object key order don't
+  /// affect the hash, so two definitions that wire the same actors the same
+  /// way hash equal. Hosts store it next to whatever they record per run to
+  /// tell whether a replay would execute different logic.
+  pub fn content_hash(&self) -> String {
+    let mut nodes: Vec<&Node> = self.nodes.iter().collect();
+    nodes.sort_by(|a, b| a.id.cmp(&b.id));
+    let mut edges: Vec<&Edge> = self.edges.iter().collect();
+    edges.sort_by(|a, b| (&a.from, &a.to, a.kind).cmp(&(&b.from, &b.to, b.kind)));
+
+    let mut hasher = Sha256::new();
+    write_field(&mut hasher, "entry", &self.entry);
+    for node in nodes {
+      write_field(&mut hasher, "node.id", &node.id);
+      write_field(&mut hasher, "node.actor", &node.actor);
+      let mut config = Vec::new();
+      write_canonical(&mut config, &node.config);
+      hasher.update(b"node.config");
+      hasher.update(&config);
+    }
+    for edge in edges {
+      write_field(&mut hasher, "edge.from", &edge.from);
+      write_field(&mut hasher, "edge.to", &edge.to);
+      write_field(
+        &mut hasher,
+        "edge.kind",
+        match edge.kind {
+          EdgeKind::Data => "data",
+          EdgeKind::OnError => "on_error",
+        },
+      );
+    }
+
+    hasher
+      .finalize()
+      .iter()
+      .map(|b| format!("{b:02x}"))
+      .collect()
+  }
+
+  /// Structural difference from `self` (old) to `new`: entry change,
+  /// added/removed/changed nodes, and added/removed edges. Nodes are matched
+  /// by id; configs are compared as JSON values, so key order is ignored.
+  /// Both sides are compared as multisets: a duplicated edge delivers every
+  /// message twice, so an extra copy is reported as added (and a dropped copy
+  /// as removed). Every list in the result is sorted, so the report is
+  /// stable.
+  pub fn diff(&self, new: &Graph) -> GraphDiff {
+    // The report owns its ids and edges so it can outlive both graphs.
+    let mut diff = GraphDiff {
+      entry: (self.entry != new.entry).then(|| (self.entry.clone(), new.entry.clone())),
+      ..GraphDiff::default()
+    };
+
+    let old_nodes = group(&self.nodes, |n| n.id.as_str());
+    let new_nodes = group(&new.nodes, |n| n.id.as_str());
+    for (id, olds, news) in paired(&old_nodes, &new_nodes) {
+      let change = olds.iter().zip(news).find_map(|(old, new)| {
+        let actor = (old.actor != new.actor).then(|| (old.actor.clone(), new.actor.clone()));
+        let config_changed = old.config != new.config;
+        (actor.is_some() || config_changed).then(|| NodeChange {
+          id: (*id).to_owned(),
+          actor,
+          config_changed,
+        })
+      });
+      diff.changed_nodes.extend(change);
+      for _ in news.len()..olds.len() {
+        diff.removed_nodes.push((*id).to_owned());
+      }
+      for _ in olds.len()..news.len() {
+        diff.added_nodes.push((*id).to_owned());
+      }
+    }
+
+    let old_edges = group(&self.edges, |e| (e.from.as_str(), e.to.as_str(), e.kind));
+    let new_edges = group(&new.edges, |e| (e.from.as_str(), e.to.as_str(), e.kind));
+    for (_, olds, news) in paired(&old_edges, &new_edges) {
+      let removed = olds.get(news.len()..).unwrap_or_default();
+      let added = news.get(olds.len()..).unwrap_or_default();
+      diff
+        .removed_edges
+        .extend(removed.iter().map(|e| (*e).clone()));
+      diff.added_edges.extend(added.iter().map(|e| (*e).clone()));
+    }
+
+    diff
+  }
+}
+
+/// Bucket `items` by `key`, keeping duplicates.
+fn group<'a, T, K: Ord>(items: &'a [T], key: impl Fn(&'a T) -> K) -> BTreeMap<K, Vec<&'a T>> {
+  let mut groups: BTreeMap<K, Vec<&T>> = BTreeMap::new();
+  for item in items {
+    groups.entry(key(item)).or_default().push(item);
+  }
+  groups
+}
+
+/// Every key of either side, in order, with both sides' buckets (empty when
+/// absent).
+fn paired<'m, K: Ord, T>(
+  old: &'m BTreeMap<K, Vec<T>>,
+  new: &'m BTreeMap<K, Vec<T>>,
+) -> impl Iterator<Item = (&'m K, &'m [T], &'m [T])> {
+  let keys: BTreeSet<&K> = old.keys().chain(new.keys()).collect();
+  keys.into_iter().map(move |k| {
+    let side = |m: &'m BTreeMap<K, Vec<T>>| m.get(k).map_or(&[][..], Vec::as_slice);
+    (k, side(old), side(new))
+  })
+}
+
+/// Result of [`Graph::diff`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct GraphDiff {
+  /// `(old, new)` entry node id, when it moved.
+  pub entry: Option<(String, String)>,
+  pub added_nodes: Vec<String>,
+  pub removed_nodes: Vec<String>,
+  pub changed_nodes: Vec<NodeChange>,
+  pub added_edges: Vec<Edge>,
+  pub removed_edges: Vec<Edge>,
+}
+
+impl GraphDiff {
+  /// True when the two graphs are structurally identical.
+  pub fn is_empty(&self) -> bool {
+    self.entry.is_none()
+      && self.added_nodes.is_empty()
+      && self.removed_nodes.is_empty()
+      && self.changed_nodes.is_empty()
+      && self.added_edges.is_empty()
+      && self.removed_edges.is_empty()
+  }
+}
+
+/// A node present in both graphs whose actor or config differs.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct NodeChange {
+  pub id: String,
+  /// `(old, new)` actor key, when the node now runs a different actor.
+  pub actor: Option<(String, String)>,
+  pub config_changed: bool,
+}
+
+/// Length-prefixed so adjacent fields can't run together ambiguously.
+fn write_field(hasher: &mut Sha256, name: &str, value: &str) {
+  hasher.update(name.as_bytes());
+  hasher.update((value.len() as u64).to_le_bytes());
+  hasher.update(value.as_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  fn graph(value: Value) -> Graph {
+    serde_json::from_value(value).unwrap()
+  }
+
+  #[test]
+  fn content_hash_ignores_ordering() {
+    let a = graph(json!({
+      "entry": "in",
+      "nodes": [
+        { "id": "in", "actor": "src", "config": { "a": 1, "b": [1, 2] } },
+        { "id": "out", "actor": "sink" }
+      ],
+      "edges": [{ "from": "in", "to": "out" }]
+    }));
+    let b = graph(json!({
+      "entry": "in",
+      "nodes": [
+        { "id": "out", "actor": "sink" },
+        { "id": "in", "actor": "src", "config": { "b": [1, 2], "a": 1 } }
+      ],
+      "edges": [{ "from": "in", "to": "out", "kind": "data" }]
+    }));
+    assert_eq!(a.content_hash(), b.content_hash());
+    assert_eq!(a.content_hash().len(), 64);
+  }
+
+  #[test]
+  fn content_hash_detects_changes() {
+    let base = json!({
+      "entry": "in",
+      "nodes": [
+        { "id": "in", "actor": "src", "config": { "a": 1 } },
+        { "id": "out", "actor": "sink" }
+      ],
+      "edges": [{ "from": "in", "to": "out" }]
+    });
+    let original = graph(base.clone()).content_hash();
+
+    let mut config = base.clone();
+    config["nodes"][0]["config"]["a"] = json!(2);
+    assert_ne!(graph(config).content_hash(), original);
+
+    let mut actor = base.clone();
+    actor["nodes"][1]["actor"] = json!("other.sink");
+    assert_ne!(graph(actor).content_hash(), original);
+
+    let mut kind = base;
+    kind["edges"][0]["kind"] = json!("on_error");
+    assert_ne!(graph(kind).content_hash(), original);
+  }
+
+  #[cfg(feature = "schema")]
+  #[test]
+  fn json_schema_describes_graph() {
+    let schema = Graph::json_schema();
+    assert_eq!(schema["title"], "Graph");
+    assert_eq!(schema["required"], json!(["entry", "nodes", "edges"]));
+    let kind = &schema["$defs"]["EdgeKind"];
+    assert_eq!(kind["enum"], json!(["data", "on_error"]));
+  }
+
+  #[test]
+  fn diff_reports_structural_changes() {
+    let old = graph(json!({
+      "entry": "in",
+      "nodes": [
+        { "id": "in", "actor": "passthrough" },
+        { "id": "fmt", "actor": "transform", "config": { "a": 1, "b": 2 } },
+        { "id": "gone", "actor": "sink" }
+      ],
+      "edges": [
+        { "from": "in", "to": "fmt" },
+        { "from": "fmt", "to": "gone" }
+      ]
+    }));
+    let new = graph(json!({
+      "entry": "in",
+      "nodes": [
+        { "id": "fmt", "actor": "transform", "config": { "b": 2, "a": 1 } },
+        { "id": "in", "actor": "filter" },
+        { "id": "out", "actor": "sink" }
+      ],
+      "edges": [
+        { "from": "in", "to": "fmt" },
+        { "from": "fmt", "to": "out" },
+        { "from": "fmt", "to": "out", "kind": "on_error" }
+      ]
+    }));
+
+    let diff = old.diff(&new);
+    assert_eq!(diff.entry, None);
+    assert_eq!(diff.added_nodes, vec!["out"]);
+    assert_eq!(diff.removed_nodes, vec!["gone"]);
+    assert_eq!(
+      diff.changed_nodes,
+      vec![NodeChange {
+        id: "in".into(),
+        actor: Some(("passthrough".into(), "filter".into())),
+        config_changed: false,
+      }]
+    );
+    let pairs = |edges: &[Edge]| -> Vec<(String, String, EdgeKind)> {
+      edges
+        .iter()
+        .map(|e| (e.from.clone(), e.to.clone(), e.kind))
+        .collect()
+    };
+    assert_eq!(
+      pairs(&diff.added_edges),
+      vec![
+        ("fmt".into(), "out".into(), EdgeKind::Data),
+        ("fmt".into(), "out".into(), EdgeKind::OnError),
+      ]
+    );
+    assert_eq!(
+      pairs(&diff.removed_edges),
+      vec![("fmt".into(), "gone".into(), EdgeKind::Data)]
+    );
+    assert!(!diff.is_empty());
+    assert!(new.diff(&new).is_empty());
+
+    // A duplicated edge doubles delivery, so it is a change.
+    let mut doubled = new.clone();
+    doubled.edges.push(doubled.edges[0].clone());
+    let diff = new.diff(&doubled);
+    assert_eq!(
+      pairs(&diff.added_edges),
+      vec![("in".into(), "fmt".into(), EdgeKind::Data)]
+    );
+    assert!(diff.removed_edges.is_empty());
+    assert_eq!(
+      pairs(&doubled.diff(&new).removed_edges),
+      vec![("in".into(), "fmt".into(), EdgeKind::Data)]
+    );
+  }
 }
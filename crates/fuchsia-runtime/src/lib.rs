@@ -2,6 +2,6 @@ pub mod graph;
 pub mod orchestrator;
 pub mod registry;
 
-pub use graph::{Edge, Graph, Node};
-pub use orchestrator::{Orchestrator, WorkflowHandle};
+pub use graph::{Edge, EdgeKind, Graph, GraphDiff, Node, NodeChange};
+pub use orchestrator::{DEFAULT_SHUTDOWN_GRACE, NodeStatus, Orchestrator, WorkflowHandle};
 pub use registry::{ActorFactory, ActorRegistry};
@@ -1,22 +1,59 @@
-use crate::graph::Graph;
+use crate::graph::{EdgeKind, Graph};
 use crate::registry::ActorRegistry;
 use fuchsia_actor::{ActorError, Context, Emitter, Inbox, Message};
+use serde::Serialize;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
-use tokio::task::JoinHandle;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::task::{JoinError, JoinHandle};
+use tokio::time::Instant;
 use tokio_util::sync::CancellationToken;
+use tokio_util::task::AbortOnDropHandle;
 use tracing::Instrument;
 
 const CHANNEL_BUFFER: usize = 32;
 
+/// Default for [`Orchestrator::shutdown_grace`].
+pub const DEFAULT_SHUTDOWN_GRACE: Duration = Duration::from_secs(1);
+
 pub struct Orchestrator {
   registry: Arc<ActorRegistry>,
+  allowed_actors: Option<Vec<String>>,
+  shutdown_grace: Duration,
 }
 
 impl Orchestrator {
   pub fn new(registry: Arc<ActorRegistry>) -> Self {
-    Self { registry }
+    Self {
+      registry,
+      allowed_actors: None,
+      shutdown_grace: DEFAULT_SHUTDOWN_GRACE,
+    }
+  }
+
+  /// How long [`WorkflowHandle::shutdown`] waits after cancelling before it
+  /// aborts actors that are still running. Defaults to
+  /// [`DEFAULT_SHUTDOWN_GRACE`]; lengthen it for actors with slow
+  /// teardowns.
+  pub fn shutdown_grace(mut self, grace: Duration) -> Self {
+    self.shutdown_grace = grace;
+    self
+  }
+
+  /// Restrict which `node.actor` keys graphs started by this orchestrator
+  /// may use. Each pattern is an exact key or a prefix ending in `*`
+  /// (`"fuchsia.*"`). `start` rejects a graph naming any other actor with
+  /// [`ActorError::NotAllowed`] before anything is spawned. Without an
+  /// allowlist every registered actor is permitted.
+  pub fn allow_actors<I, S>(mut self, patterns: I) -> Self
+  where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+  {
+    self.allowed_actors = Some(patterns.into_iter().map(Into::into).collect());
+    self
   }
 
   #[tracing::instrument(
@@ -29,6 +66,16 @@ impl Orchestrator {
     ),
   )]
   pub fn start(&self, graph: &Graph) -> Result<WorkflowHandle, ActorError> {
+    if let Some(patterns) = &self.allowed_actors
+      && let Some(node) = graph
+        .nodes
+        .iter()
+        .find(|n| !actor_allowed(patterns, &n.actor))
+    {
+      tracing::warn!(node = %node.id, actor = %node.actor, "actor rejected by allowlist");
+      return Err(ActorError::NotAllowed(node.actor.clone()));
+    }
+
     let mut senders: HashMap<String, mpsc::Sender<Message>> = HashMap::new();
     let mut receivers: HashMap<String, mpsc::Receiver<Message>> = HashMap::new();
 
@@ -51,13 +98,20 @@ impl Orchestrator {
     }
 
     let cancel = CancellationToken::new();
-    let mut join_handles: Vec<JoinHandle<Result<(), ActorError>>> = Vec::new();
+    let mut join_handles: Vec<(String, ActorTask)> = Vec::new();
 
     for node in &graph.nodes {
       let downstream: Vec<mpsc::Sender<Message>> = graph
-        .edges_from(&node.id)
+        .edges_from_kind(&node.id, EdgeKind::Data)
         .map(|edge| senders[&edge.to].clone())
         .collect();
+      let on_error: Vec<mpsc::Sender<Message>> = graph
+        .edges_from_kind(&node.id, EdgeKind::OnError)
+        .map(|edge| senders[&edge.to].clone())
+        .collect();
+      // Only nodes with error edges need their id inside the task to label
+      // the error message.
+      let error_route = (!on_error.is_empty()).then(|| (node.id.clone(), Emitter::new(on_error)));
 
       let emit = Emitter::new(downstream);
       let inbox = Inbox::new(receivers.remove(&node.id).ok_or_else(|| {
@@ -77,18 +131,50 @@ impl Orchestrator {
       let handle = tokio::spawn(
         async move {
           tracing::debug!("actor starting");
-          let result = actor.run(inbox, emit, ctx).await;
+          // Run the actor in its own task so a panic surfaces here as a
+          // `JoinError` and is routed like any other failure. Aborting this
+          // task (shutdown) drops the guard, which aborts the inner one.
+          let inner = AbortOnDropHandle::new(tokio::spawn(
+            async move { actor.run(inbox, emit, ctx).await }.in_current_span(),
+          ));
+          let result = task_result(inner.await);
           match &result {
             Ok(()) => tracing::debug!("actor exited"),
-            Err(e) => tracing::error!(error = %e, "actor exited with error"),
+            Err(e) => {
+              tracing::error!(error = %e, "actor exited with error");
+              if let Some((node_id, errors)) = error_route {
+                let msg = Message::with_type("error").json(serde_json::json!({
+                  "node": node_id,
+                  "error": e.to_string(),
+                }));
+                if let Err(send_err) = errors.send(msg).await {
+                  tracing::warn!(error = %send_err, "failed to route actor error");
+                }
+              }
+            }
           }
           result
         }
         .instrument(span),
       );
-      join_handles.push(handle);
+      join_handles.push((node.id.clone(), handle));
     }
 
+    // Weak senders let `send_to` reach any node without holding its channel
+    // open, so the completion cascade still closes every inbox.
+    let nodes = senders
+      .iter()
+      .map(|(id, tx)| (id.clone(), tx.downgrade()))
+      .collect();
+    // Nodes with no incoming edges (other than the entry) are fed only by
+    // `send_to`. Nothing else holds their sender, so the handle keeps one
+    // until `join`/`shutdown`, or their inbox would close immediately.
+    let signal_only = graph
+      .nodes
+      .iter()
+      .filter(|n| n.id != graph.entry && !graph.edges.iter().any(|e| e.to == n.id))
+      .filter_map(|n| senders.get(&n.id).cloned())
+      .collect();
     let entry_sender = senders
       .remove(&graph.entry)
       .ok_or_else(|| ActorError::Other("internal: entry sender missing".into()))?;
@@ -100,16 +186,48 @@ impl Orchestrator {
 
     Ok(WorkflowHandle {
       entry: Some(entry_sender),
+      nodes,
+      signal_only,
       cancel,
       join_handles,
+      shutdown_grace: self.shutdown_grace,
     })
   }
 }
 
+fn actor_allowed(patterns: &[String], actor: &str) -> bool {
+  patterns.iter().any(|p| match p.strip_suffix('*') {
+    Some(prefix) => actor.starts_with(prefix),
+    None => p == actor,
+  })
+}
+
+/// A running workflow, returned by [`Orchestrator::start`].
+///
+/// Nodes with no incoming edges (other than the entry) keep their inbox
+/// open for [`send_to`](Self::send_to) until [`join`](Self::join) or
+/// [`shutdown`](Self::shutdown). A source actor that waits for its inbox to
+/// close before finishing therefore runs until then, instead of seeing the
+/// inbox close at start.
 pub struct WorkflowHandle {
   entry: Option<mpsc::Sender<Message>>,
+  nodes: HashMap<String, mpsc::WeakSender<Message>>,
+  signal_only: Vec<mpsc::Sender<Message>>,
   cancel: CancellationToken,
-  join_handles: Vec<JoinHandle<Result<(), ActorError>>>,
+  join_handles: Vec<(String, ActorTask)>,
+  shutdown_grace: Duration,
+}
+
+type ActorTask = JoinHandle<Result<(), ActorError>>;
+
+/// Whether one node's actor task is still running. Returned by
+/// [`WorkflowHandle::status`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NodeStatus {
+  pub node: String,
+  /// `false` once `run` has returned, errored, or panicked. The outcome is
+  /// reported by `join`.
+  pub running: bool,
 }
 
 impl WorkflowHandle {
@@ -126,12 +244,68 @@ impl WorkflowHandle {
       .map_err(|e| ActorError::Send(e.to_string()))
   }
 
+  /// Push a message into the entry node without waiting for capacity.
+  /// Returns [`ActorError::Full`] when the entry channel is saturated, so
+  /// hosts fronting the workflow (webhooks, pollers) can shed load instead
+  /// of blocking.
+  #[tracing::instrument(name = "workflow.try_send", level = "trace", skip_all)]
+  pub fn try_send(&self, msg: Message) -> Result<(), ActorError> {
+    let entry = self
+      .entry
+      .as_ref()
+      .ok_or_else(|| ActorError::Other("entry already closed".into()))?;
+    entry.try_send(msg).map_err(|e| match e {
+      TrySendError::Full(_) => ActorError::Full,
+      TrySendError::Closed(_) => ActorError::Send("channel closed".into()),
+    })
+  }
+
+  /// Push a message straight into the inbox of node `node`, bypassing the
+  /// entry. Hosts use it to deliver out-of-band events — a webhook
+  /// callback, an approval — to an actor waiting on them mid-workflow.
+  /// Nodes with no incoming edges stay open for `send_to` until `join` or
+  /// `shutdown`. Fails with [`ActorError::UnknownNode`] for an id not in the
+  /// graph, and [`ActorError::Send`] once the node's inbox has closed — its
+  /// upstream senders are gone or the actor has exited.
+  #[tracing::instrument(name = "workflow.send_to", level = "trace", skip(self, msg))]
+  pub async fn send_to(&self, node: &str, msg: Message) -> Result<(), ActorError> {
+    let tx = self
+      .nodes
+      .get(node)
+      .ok_or_else(|| ActorError::UnknownNode(node.to_string()))?
+      .upgrade()
+      .ok_or_else(|| ActorError::Send("channel closed".into()))?;
+    tx.send(msg)
+      .await
+      .map_err(|e| ActorError::Send(e.to_string()))
+  }
+
+  /// Free slots in the entry channel. Zero means the next `send` will wait
+  /// and the next `try_send` will fail with [`ActorError::Full`].
+  pub fn entry_capacity(&self) -> usize {
+    self.entry.as_ref().map_or(0, |entry| entry.capacity())
+  }
+
   /// Trigger cancellation. All actors observing `ctx.cancelled()` will exit.
   pub fn cancel(&self) {
     tracing::debug!("workflow.cancel");
     self.cancel.cancel();
   }
 
+  /// Snapshot of every node, in spawn order — the same order `join` and
+  /// `shutdown` report results in. Lets a host show which actors have
+  /// already exited without waiting for the workflow to finish.
+  pub fn status(&self) -> Vec<NodeStatus> {
+    self
+      .join_handles
+      .iter()
+      .map(|(node, handle)| NodeStatus {
+        node: node.clone(),
+        running: !handle.is_finished(),
+      })
+      .collect()
+  }
+
   /// Close the entry channel and wait for every actor task to finish.
   /// Returns one result per actor, in spawn order.
   #[tracing::instrument(name = "workflow.join", skip_all, fields(actors = self.join_handles.len()))]
@@ -139,15 +313,63 @@ impl WorkflowHandle {
     // Dropping the entry sender lets the entry actor's inbox drain and close,
     // which cascades to all downstreams.
     self.entry = None;
+    self.signal_only.clear();
 
     let mut results = Vec::with_capacity(self.join_handles.len());
-    for handle in self.join_handles.drain(..) {
-      match handle.await {
-        Ok(r) => results.push(r),
-        Err(_) => results.push(Err(ActorError::Panic)),
-      }
+    for (_, handle) in self.join_handles.drain(..) {
+      results.push(task_result(handle.await));
     }
     tracing::info!("workflow joined");
     results
   }
+
+  /// Close the entry channel and let in-flight messages drain for up to
+  /// `deadline`. Actors still running when it elapses are cancelled, and any
+  /// that haven't exited [`Orchestrator::shutdown_grace`] later — an actor that never
+  /// polls `ctx.cancelled()` — are aborted and reported as
+  /// [`ActorError::Aborted`]. Returns one result per actor, in spawn order,
+  /// like [`join`](Self::join).
+  #[tracing::instrument(name = "workflow.shutdown", skip_all, fields(actors = self.join_handles.len()))]
+  pub async fn shutdown(mut self, deadline: Duration) -> Vec<Result<(), ActorError>> {
+    self.entry = None;
+    self.signal_only.clear();
+
+    let drain_until = Instant::now() + deadline;
+    let abort_at = drain_until + self.shutdown_grace;
+    let mut results = Vec::with_capacity(self.join_handles.len());
+    for (node, mut handle) in self.join_handles.drain(..) {
+      // `timeout_at` polls the task before the timer, so tasks that already
+      // finished are collected even after the deadline has passed.
+      let joined = match tokio::time::timeout_at(drain_until, &mut handle).await {
+        Ok(joined) => joined,
+        Err(_) => {
+          if !self.cancel.is_cancelled() {
+            tracing::warn!("drain deadline elapsed; cancelling workflow");
+            self.cancel.cancel();
+          }
+          match tokio::time::timeout_at(abort_at, &mut handle).await {
+            Ok(joined) => joined,
+            Err(_) => {
+              tracing::warn!(node = %node, "actor ignored cancellation; aborting");
+              handle.abort();
+              results.push(Err(ActorError::Aborted));
+              continue;
+            }
+          }
+        }
+      };
+      results.push(task_result(joined));
+    }
+    tracing::info!("workflow shut down");
+    results
+  }
+}
+
+/// Flatten a joined actor task; a task that panicked reports
+/// [`ActorError::Panic`].
+fn task_result(joined: Result<Result<(), ActorError>, JoinError>) -> Result<(), ActorError> {
+  match joined {
+    Ok(r) => r,
+    Err(_) => Err(ActorError::Panic),
+  }
 }
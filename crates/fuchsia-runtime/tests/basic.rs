@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 use fuchsia_actor::{Actor, ActorError, Context, Emitter, Inbox, Message, MessageValue};
-use fuchsia_runtime::{ActorRegistry, Edge, Graph, Node, Orchestrator};
+use fuchsia_runtime::{ActorRegistry, Edge, EdgeKind, Graph, Node, Orchestrator};
 use serde::Deserialize;
 use serde_json::{Value, json};
 use std::sync::{Arc, Mutex};
@@ -66,6 +66,56 @@ impl Actor for Recorder {
   }
 }
 
+/// Fails on the first message it receives.
+struct Failer;
+
+#[async_trait]
+impl Actor for Failer {
+  async fn run(&self, mut inbox: Inbox, _emit: Emitter, ctx: Context) -> Result<(), ActorError> {
+    tokio::select! {
+        _ = ctx.cancelled() => Ok(()),
+        msg = inbox.recv() => match msg {
+            Some(_) => Err(ActorError::Other("boom".into())),
+            None => Ok(()),
+        }
+    }
+  }
+}
+
+/// Panics on the first message it receives.
+struct Panicker;
+
+#[async_trait]
+impl Actor for Panicker {
+  async fn run(&self, mut inbox: Inbox, _emit: Emitter, _ctx: Context) -> Result<(), ActorError> {
+    if inbox.recv().await.is_some() {
+      panic!("boom");
+    }
+    Ok(())
+  }
+}
+
+/// Never reads its inbox; holds the channel full until cancelled.
+struct Stall;
+
+#[async_trait]
+impl Actor for Stall {
+  async fn run(&self, _inbox: Inbox, _emit: Emitter, ctx: Context) -> Result<(), ActorError> {
+    ctx.cancelled().await;
+    Ok(())
+  }
+}
+
+/// Ignores its inbox and cancellation alike; only an abort stops it.
+struct Deaf;
+
+#[async_trait]
+impl Actor for Deaf {
+  async fn run(&self, _inbox: Inbox, _emit: Emitter, _ctx: Context) -> Result<(), ActorError> {
+    std::future::pending().await
+  }
+}
+
 struct Debouncer {
   window: Duration,
 }
@@ -116,6 +166,10 @@ fn build_registry(out: Arc<Mutex<Vec<Message>>>) -> ActorRegistry {
   reg.register::<Debouncer, DebouncerConfig, _>("debounce", |cfg: DebouncerConfig| Debouncer {
     window: Duration::from_millis(cfg.window_ms),
   });
+  reg.register::<Failer, Value, _>("fail", |_| Failer);
+  reg.register::<Stall, Value, _>("stall", |_| Stall);
+  reg.register::<Panicker, Value, _>("panic", |_| Panicker);
+  reg.register::<Deaf, Value, _>("deaf", |_| Deaf);
   reg.register::<Recorder, Value, _>("recorder", move |_| Recorder { out: out.clone() });
   reg
 }
@@ -132,6 +186,14 @@ fn edge(from: &str, to: &str) -> Edge {
   Edge {
     from: from.into(),
     to: to.into(),
+    kind: EdgeKind::Data,
+  }
+}
+
+fn error_edge(from: &str, to: &str) -> Edge {
+  Edge {
+    kind: EdgeKind::OnError,
+    ..edge(from, to)
   }
 }
 
@@ -379,6 +441,305 @@ async fn cancellation_exits_cleanly() {
   assert_all_ok(&results);
 }
 
+#[tokio::test]
+async fn shutdown_drains_in_flight_messages() {
+  let out = Arc::new(Mutex::new(Vec::new()));
+  let registry = build_registry(out.clone());
+
+  let graph = Graph {
+    entry: "in".into(),
+    nodes: vec![
+      node("in", "passthrough", Value::Null),
+      node("rec", "recorder", Value::Null),
+    ],
+    edges: vec![edge("in", "rec")],
+  };
+
+  let orchestrator = Orchestrator::new(Arc::new(registry));
+  let handle = orchestrator.start(&graph).unwrap();
+  for i in 0..10 {
+    handle
+      .send(Message::with_type("test").json(json!(i)))
+      .await
+      .unwrap();
+  }
+
+  let results = handle.shutdown(Duration::from_secs(5)).await;
+  assert_all_ok(&results);
+  assert_eq!(out.lock().unwrap().len(), 10);
+}
+
+#[tokio::test]
+async fn shutdown_cancels_after_deadline() {
+  let out = Arc::new(Mutex::new(Vec::new()));
+  let registry = build_registry(out.clone());
+
+  let graph = Graph {
+    entry: "stall".into(),
+    nodes: vec![node("stall", "stall", Value::Null)],
+    edges: vec![],
+  };
+
+  let orchestrator = Orchestrator::new(Arc::new(registry));
+  let handle = orchestrator.start(&graph).unwrap();
+  handle
+    .send(Message::with_type("test").json(json!(1)))
+    .await
+    .unwrap();
+
+  let results = handle.shutdown(Duration::from_millis(20)).await;
+  assert_all_ok(&results);
+}
+
+#[tokio::test]
+async fn send_to_reaches_inner_node() {
+  let out = Arc::new(Mutex::new(Vec::new()));
+  let registry = build_registry(out.clone());
+
+  let graph = Graph {
+    entry: "in".into(),
+    nodes: vec![
+      node("in", "passthrough", Value::Null),
+      node("rec", "recorder", Value::Null),
+    ],
+    edges: vec![edge("in", "rec")],
+  };
+
+  let orchestrator = Orchestrator::new(Arc::new(registry));
+  let handle = orchestrator.start(&graph).unwrap();
+
+  handle
+    .send_to("rec", Message::with_type("signal").json(json!("approved")))
+    .await
+    .unwrap();
+  match handle
+    .send_to("missing", Message::with_type("signal").json(json!(1)))
+    .await
+  {
+    Err(ActorError::UnknownNode(id)) => assert_eq!(id, "missing"),
+    other => panic!("expected UnknownNode, got {other:?}"),
+  }
+
+  // The handle's weak senders must not keep `rec` alive past the cascade.
+  let results = handle.join().await;
+  assert_all_ok(&results);
+  let recorded = out.lock().unwrap();
+  assert_eq!(recorded.len(), 1);
+  assert_eq!(recorded[0].type_, "signal");
+}
+
+#[tokio::test(start_paused = true)]
+async fn send_to_reaches_signal_only_node() {
+  let out = Arc::new(Mutex::new(Vec::new()));
+  let registry = build_registry(out.clone());
+
+  // `approval` has no upstream edges; only `send_to` feeds it.
+  let graph = Graph {
+    entry: "in".into(),
+    nodes: vec![
+      node("in", "passthrough", Value::Null),
+      node("approval", "passthrough", Value::Null),
+      node("rec", "recorder", Value::Null),
+    ],
+    edges: vec![edge("in", "rec"), edge("approval", "rec")],
+  };
+
+  let orchestrator = Orchestrator::new(Arc::new(registry));
+  let handle = orchestrator.start(&graph).unwrap();
+  // The paused clock only advances once every task is idle, so by the time
+  // this returns `approval` would have seen its inbox close.
+  tokio::time::sleep(Duration::from_millis(20)).await;
+  assert!(handle.status()[1].running, "signal-only node exited early");
+
+  handle
+    .send_to("approval", Message::with_type("approved").json(json!(true)))
+    .await
+    .unwrap();
+
+  let results = handle.join().await;
+  assert_all_ok(&results);
+  let recorded = out.lock().unwrap();
+  assert_eq!(recorded.len(), 1);
+  assert_eq!(recorded[0].type_, "approved");
+}
+
+#[tokio::test(start_paused = true)]
+async fn shutdown_aborts_actor_ignoring_cancellation() {
+  let out = Arc::new(Mutex::new(Vec::new()));
+  let registry = build_registry(out.clone());
+
+  let graph = Graph {
+    entry: "in".into(),
+    nodes: vec![
+      node("in", "passthrough", Value::Null),
+      node("deaf", "deaf", Value::Null),
+    ],
+    edges: vec![edge("in", "deaf")],
+  };
+
+  let grace = Duration::from_millis(200);
+  let orchestrator = Orchestrator::new(Arc::new(registry)).shutdown_grace(grace);
+  let handle = orchestrator.start(&graph).unwrap();
+
+  let started = tokio::time::Instant::now();
+  let results = handle.shutdown(Duration::from_millis(50)).await;
+  // The clock is paused, so it advances exactly to the abort deadline.
+  assert_eq!(started.elapsed(), Duration::from_millis(50) + grace);
+  assert!(results[0].is_ok());
+  assert!(matches!(results[1], Err(ActorError::Aborted)));
+}
+
+#[tokio::test]
+async fn on_error_edge_receives_failure() {
+  let out = Arc::new(Mutex::new(Vec::new()));
+  let registry = build_registry(out.clone());
+
+  let graph = Graph {
+    entry: "in".into(),
+    nodes: vec![
+      node("in", "passthrough", Value::Null),
+      node("work", "fail", Value::Null),
+      node("alert", "recorder", Value::Null),
+    ],
+    edges: vec![edge("in", "work"), error_edge("work", "alert")],
+  };
+
+  let orchestrator = Orchestrator::new(Arc::new(registry));
+  let handle = orchestrator.start(&graph).unwrap();
+
+  handle
+    .send(Message::with_type("test").json(json!(1)))
+    .await
+    .unwrap();
+
+  let results = handle.join().await;
+  assert!(matches!(&results[1], Err(ActorError::Other(m)) if m == "boom"));
+
+  let recorded = out.lock().unwrap();
+  assert_eq!(recorded.len(), 1, "got {recorded:?}");
+  assert_eq!(recorded[0].type_, "error");
+  let MessageValue::Json(v) = &recorded[0].value else {
+    panic!("expected JSON error payload, got {:?}", recorded[0].value);
+  };
+  assert_eq!(v["node"], json!("work"));
+  assert_eq!(v["error"], json!("boom"));
+}
+
+#[tokio::test]
+async fn status_reports_exited_nodes() {
+  let out = Arc::new(Mutex::new(Vec::new()));
+  let registry = build_registry(out.clone());
+
+  let graph = Graph {
+    entry: "in".into(),
+    nodes: vec![
+      node("in", "passthrough", Value::Null),
+      node("work", "fail", Value::Null),
+    ],
+    edges: vec![edge("in", "work")],
+  };
+
+  let orchestrator = Orchestrator::new(Arc::new(registry));
+  let handle = orchestrator.start(&graph).unwrap();
+  let ids: Vec<String> = handle.status().into_iter().map(|s| s.node).collect();
+  assert_eq!(ids, ["in", "work"]);
+
+  handle
+    .send(Message::with_type("test").json(json!(1)))
+    .await
+    .unwrap();
+  for _ in 0..100 {
+    if !handle.status()[1].running {
+      break;
+    }
+    tokio::time::sleep(Duration::from_millis(5)).await;
+  }
+  let status = handle.status();
+  assert!(status[0].running);
+  assert!(!status[1].running);
+
+  handle.cancel();
+  let results = handle.join().await;
+  assert!(matches!(&results[1], Err(ActorError::Other(m)) if m == "boom"));
+}
+
+#[tokio::test]
+async fn on_error_edge_receives_panic() {
+  let out = Arc::new(Mutex::new(Vec::new()));
+  let registry = build_registry(out.clone());
+
+  let graph = Graph {
+    entry: "in".into(),
+    nodes: vec![
+      node("in", "passthrough", Value::Null),
+      node("work", "panic", Value::Null),
+      node("alert", "recorder", Value::Null),
+    ],
+    edges: vec![edge("in", "work"), error_edge("work", "alert")],
+  };
+
+  let orchestrator = Orchestrator::new(Arc::new(registry));
+  let handle = orchestrator.start(&graph).unwrap();
+
+  handle
+    .send(Message::with_type("test").json(json!(1)))
+    .await
+    .unwrap();
+
+  let results = handle.join().await;
+  assert!(matches!(results[1], Err(ActorError::Panic)));
+
+  let recorded = out.lock().unwrap();
+  assert_eq!(recorded.len(), 1, "got {recorded:?}");
+  let MessageValue::Json(v) = &recorded[0].value else {
+    panic!("expected JSON error payload, got {:?}", recorded[0].value);
+  };
+  assert_eq!(v["node"], json!("work"));
+  assert_eq!(v["error"], json!("actor task panicked"));
+}
+
+#[test]
+fn edge_kind_defaults_to_data() {
+  let edge: Edge = serde_json::from_value(json!({ "from": "a", "to": "b" })).unwrap();
+  assert_eq!(edge.kind, EdgeKind::Data);
+  let edge: Edge =
+    serde_json::from_value(json!({ "from": "a", "to": "b", "kind": "on_error" })).unwrap();
+  assert_eq!(edge.kind, EdgeKind::OnError);
+}
+
+#[tokio::test]
+async fn try_send_reports_full_entry() {
+  let out = Arc::new(Mutex::new(Vec::new()));
+  let registry = build_registry(out.clone());
+
+  let graph = Graph {
+    entry: "stall".into(),
+    nodes: vec![node("stall", "stall", Value::Null)],
+    edges: vec![],
+  };
+
+  let orchestrator = Orchestrator::new(Arc::new(registry));
+  let handle = orchestrator.start(&graph).unwrap();
+
+  let capacity = handle.entry_capacity();
+  assert!(capacity > 0);
+  for i in 0..capacity {
+    handle
+      .try_send(Message::with_type("test").json(json!(i)))
+      .unwrap();
+  }
+  assert_eq!(handle.entry_capacity(), 0);
+
+  match handle.try_send(Message::with_type("test").json(json!("overflow"))) {
+    Err(ActorError::Full) => {}
+    other => panic!("expected Full, got {other:?}"),
+  }
+
+  handle.cancel();
+  let results = handle.join().await;
+  assert_all_ok(&results);
+}
+
 #[tokio::test]
 async fn unknown_actor_is_reported() {
   let registry = ActorRegistry::new();
@@ -394,3 +755,35 @@ async fn unknown_actor_is_reported() {
     Ok(_) => panic!("expected error, got Ok"),
   }
 }
+
+#[tokio::test]
+async fn allowlist_rejects_unlisted_actor() {
+  let out = Arc::new(Mutex::new(Vec::new()));
+  let registry = Arc::new(build_registry(out.clone()));
+  let graph = Graph {
+    entry: "in".into(),
+    nodes: vec![
+      node("in", "passthrough", Value::Null),
+      node("rec", "recorder", Value::Null),
+    ],
+    edges: vec![edge("in", "rec")],
+  };
+
+  let orchestrator = Orchestrator::new(registry.clone()).allow_actors(["pass*"]);
+  match orchestrator.start(&graph) {
+    Err(ActorError::NotAllowed(actor)) => assert_eq!(actor, "recorder"),
+    Err(e) => panic!("expected NotAllowed, got {e:?}"),
+    Ok(_) => panic!("expected error, got Ok"),
+  }
+
+  let handle = Orchestrator::new(registry)
+    .allow_actors(["pass*", "recorder"])
+    .start(&graph)
+    .expect("allowed graph starts");
+  handle
+    .send(Message::with_type("t").json(json!(1)))
+    .await
+    .unwrap();
+  assert_all_ok(&handle.join().await);
+  assert_eq!(out.lock().unwrap().len(), 1);
+}